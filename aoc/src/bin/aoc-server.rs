@@ -0,0 +1,31 @@
+use aoc::config::{self, Config};
+use aoc::{registry, server};
+use clap::Parser;
+use std::error::Error;
+use std::net::{Ipv4Addr, TcpListener};
+use std::time::Duration;
+
+/// Serves the Advent of Code solutions on a local port: `POST /{year}/day/{n}/part/{p}` with the
+/// input as body answers with the result and timing of the part as JSON.
+#[derive(Parser)]
+#[command(name = "aoc-server")]
+struct Cli {
+    /// Port to listen on, on the loopback interface only
+    #[arg(short, long, default_value_t = 8025)]
+    port: u16,
+    /// Stop each part after this many seconds, and report it as timed out [default: the configured
+    /// timeout, if any]
+    #[arg(short, long, value_parser = config::parse_seconds)]
+    timeout: Option<Duration>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    common::init_logging();
+    let config = Config::load_default()?;
+    config.apply_params(registry::latest(), &[], &[])?;
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, cli.port))?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    server::serve(listener, config.timeout(cli.timeout))?;
+    Ok(())
+}
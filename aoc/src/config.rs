@@ -0,0 +1,277 @@
+use crate::registry::Year;
+use crate::{registry, scaffold};
+use common::{BenchConfig, Budget, INPUT_DIR_VAR, InputSource, OutputFormat};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Default name of the configuration file, in the workspace root.
+pub const FILE_NAME: &str = "aoc.toml";
+/// Environment variable overriding the path of the configuration file.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+/// Settings of the runner, from `aoc.toml`. The command line flags override them.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Directory containing the inputs, relative to the configuration file
+    pub input_dir: Option<PathBuf>,
+    #[serde(default, deserialize_with = "from_str")]
+    pub format: Option<OutputFormat>,
+    /// Maximum duration of each part, in seconds
+    #[serde(default, deserialize_with = "seconds")]
+    pub timeout: Option<Duration>,
+    #[serde(default)]
+    pub bench: BenchSettings,
+    /// Values of the days' parameters, by year then `day_N`, e.g. `[params.2025.day_8]`
+    #[serde(default)]
+    pub params: BTreeMap<String, BTreeMap<String, BTreeMap<String, toml::Value>>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchSettings {
+    /// Number of timed runs per part
+    pub runs: Option<u32>,
+    /// Seconds of timed runs per part, instead of a number of runs
+    #[serde(default, deserialize_with = "seconds")]
+    pub time: Option<Duration>,
+    pub warmup: Option<u32>,
+}
+
+fn from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+fn seconds<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let secs = f64::deserialize(deserializer)?;
+    to_duration(secs)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// A positive number of seconds, which fits in a [Duration].
+fn to_duration(secs: f64) -> Result<Duration, String> {
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| format!("{} is not a positive number of seconds", secs))
+}
+
+impl Config {
+    /// The file from [CONFIG_VAR] if set, which must exist, or else `aoc.toml` in the workspace
+    /// root if there is one.
+    pub fn load_default() -> Result<Config, Box<dyn Error>> {
+        match env::var_os(CONFIG_VAR) {
+            Some(path) => Config::load(Path::new(&path)),
+            None => {
+                let path = scaffold::default_workspace().join(FILE_NAME);
+                match Config::load(&path) {
+                    Err(_) if !path.exists() => Ok(Config::default()),
+                    config => config,
+                }
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Config, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read config {}: {}", path.display(), e))?;
+        let mut config = Config::parse(&text)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        if let (Some(dir), Some(base)) = (&config.input_dir, path.parent()) {
+            config.input_dir = Some(base.join(dir));
+        }
+        Ok(config)
+    }
+
+    fn parse(text: &str) -> Result<Config, Box<dyn Error>> {
+        let config = toml::from_str::<Config>(text)?;
+        if config.bench.runs.is_some() && config.bench.time.is_some() {
+            return Err("`bench.runs` and `bench.time` are exclusive".into());
+        }
+        Ok(config)
+    }
+
+    /// `dir` if given, or else the directory from [INPUT_DIR_VAR], from the configuration or the
+    /// workspace root, in this order.
+    pub fn input_source(&self, dir: Option<PathBuf>) -> InputSource {
+        match (dir, &self.input_dir) {
+            (Some(dir), _) => InputSource::Dir(dir),
+            (None, Some(dir)) if env::var_os(INPUT_DIR_VAR).is_none() => {
+                InputSource::Dir(dir.clone())
+            }
+            (None, _) => InputSource::default_dir(),
+        }
+    }
+
+    pub fn timeout(&self, timeout: Option<Duration>) -> Option<Duration> {
+        timeout.or(self.timeout)
+    }
+
+    /// The benchmark settings, with `runs`, `time` and `warmup` from the command line taking
+    /// precedence over the configured ones.
+    pub fn bench_config(
+        &self,
+        runs: Option<u32>,
+        time: Option<Duration>,
+        warmup: Option<u32>,
+    ) -> BenchConfig {
+        let budget = match (runs, time, self.bench.runs, self.bench.time) {
+            (_, Some(time), _, _) | (None, None, _, Some(time)) => Budget::Time(time),
+            (Some(runs), None, _, _) | (None, None, Some(runs), None) => Budget::Runs(runs),
+            (None, None, None, None) => BenchConfig::default().budget,
+        };
+        let warmup = warmup
+            .or(self.bench.warmup)
+            .unwrap_or(BenchConfig::default().warmup);
+        BenchConfig { warmup, budget }
+    }
+
+    /// Overrides the parameters of the days of all the years with the configured values, then
+    /// those of the `selected` days of `year` with the `overrides` from the command line, which
+    /// must each be declared by at least one of them.
+    pub fn apply_params(
+        &self,
+        year: &Year,
+        selected: &[u8],
+        overrides: &[(String, String)],
+    ) -> Result<(), Box<dyn Error>> {
+        for (year_name, days) in &self.params {
+            let Some(configured) = year_name.parse().ok().and_then(registry::year) else {
+                return Err(
+                    format!("Invalid config: no solutions for year {:?}", year_name).into(),
+                );
+            };
+            for day_name in days.keys() {
+                let day = day_name.strip_prefix("day_").and_then(|d| d.parse().ok());
+                if day.and_then(|day| configured.find(day)).is_none() {
+                    return Err(format!(
+                        "Invalid config: no solution for {:?} of {}",
+                        day_name, configured.year
+                    )
+                    .into());
+                }
+            }
+        }
+        for (name, _) in overrides {
+            let declared = year
+                .solutions
+                .iter()
+                .filter(|s| selected.contains(&s.day()))
+                .any(|s| s.params().iter().any(|p| p.name == name));
+            if !declared {
+                return Err(format!("None of the selected days has a parameter `{}`", name).into());
+            }
+        }
+
+        for configured in registry::YEARS {
+            for solution in configured.solutions {
+                let mut values = self
+                    .params
+                    .get(&configured.year.to_string())
+                    .and_then(|days| days.get(&format!("day_{}", solution.day())))
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, value)| {
+                        let value = match value {
+                            toml::Value::String(s) => s.clone(),
+                            value => value.to_string(),
+                        };
+                        (name.clone(), value)
+                    })
+                    .collect::<Vec<_>>();
+                if configured.year == year.year && selected.contains(&solution.day()) {
+                    values.extend(
+                        overrides
+                            .iter()
+                            .filter(|(name, _)| solution.params().iter().any(|p| p.name == *name))
+                            .cloned(),
+                    );
+                }
+                solution
+                    .set_params(&values)
+                    .map_err(|e| format!("Invalid config: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a duration in seconds, like `2.5`.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs = s
+        .parse()
+        .map_err(|_| format!("{:?} is not a number of seconds", s))?;
+    to_duration(secs)
+}
+
+/// Parses a `NAME=VALUE` parameter override.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .ok_or_else(|| format!("{:?} is not a parameter like `connections=10`", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "input_dir = \"inputs\"\nformat = \"json\"\ntimeout = 60\n\n[bench]\nruns = 100\n\n\
+             [params.2025.day_8]\nconnections = 10\n",
+        )
+        .unwrap();
+        assert_eq!(config.format, Some(OutputFormat::Json));
+        assert_eq!(config.timeout(None), Some(Duration::from_secs(60)));
+        assert_eq!(
+            config.timeout(Some(Duration::from_secs(1))),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            config.bench_config(None, None, None).budget,
+            Budget::Runs(100)
+        );
+        assert_eq!(
+            config.bench_config(None, Some(Duration::from_secs(2)), Some(3)),
+            BenchConfig { warmup: 3, budget: Budget::Time(Duration::from_secs(2)) }
+        );
+        assert_eq!(
+            config.params["2025"]["day_8"]["connections"],
+            toml::Value::Integer(10)
+        );
+
+        assert!(Config::parse("format = \"xml\"\n").is_err());
+        assert!(Config::parse("timeuot = 60\n").is_err());
+        assert!(Config::parse("[bench]\nruns = 1\ntime = 2.0\n").is_err());
+        assert!(Config::parse("timeout = -1\n").is_err());
+        assert!(Config::parse("timeout = nan\n").is_err());
+        assert!(Config::parse("[bench]\ntime = -2.0\n").is_err());
+        assert!(Config::parse("[bench]\ntime = nan\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_seconds() {
+        assert_eq!(parse_seconds("2.5"), Ok(Duration::from_millis(2500)));
+        for s in ["-1", "0", "nan", "inf", "1e30", "x"] {
+            assert!(parse_seconds(s).is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::run;
+use common::itertools::Itertools;
+use common::{
+    AnySolution, InputSource, MAIN_IMPLEMENTATION as MAIN, Measurement, Part, Status, measure,
+};
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The results of all the implementations of a part on the same input, the main one first.
+pub struct CrossCheck {
+    pub day: u8,
+    pub part: Part,
+    pub runs: Vec<(&'static str, Measurement)>,
+}
+
+impl CrossCheck {
+    /// Whether the implementations which succeeded all gave the same result
+    pub fn agrees(&self) -> bool {
+        self.runs
+            .iter()
+            .filter_map(|(_, m)| m.result.as_ref())
+            .all_equal()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.agrees() && self.runs.iter().all(|(_, m)| m.is_ok())
+    }
+}
+
+/// Runs every implementation of the selected parts which have alternatives, on the same parsed
+/// input. The parts without alternatives are skipped, and an alternative named like the main
+/// implementation is an error.
+pub fn cross_check_day(
+    solution: &'static dyn AnySolution,
+    source: &InputSource,
+    parts: &[Part],
+    timeout: Option<Duration>,
+) -> Result<Vec<CrossCheck>, Box<dyn Error>> {
+    let day = solution.day();
+    let parts = parts
+        .iter()
+        .filter(|&&part| !solution.alternatives(part).is_empty())
+        .collect_vec();
+    if parts.is_empty() {
+        return Ok(Vec::new());
+    }
+    if let Some(part) = parts
+        .iter()
+        .find(|&&&part| solution.alternatives(part).contains(&MAIN))
+    {
+        return Err(format!(
+            "Day {} {}: an alternative is named `{}`, like the main implementation",
+            day, part, MAIN
+        )
+        .into());
+    }
+
+    let input = source.read(day)?;
+    let input = Arc::new(run::catch_panic(|| solution.parse_input(&input))?);
+    Ok(parts
+        .into_iter()
+        .map(|&part| {
+            let runs = [MAIN]
+                .into_iter()
+                .chain(solution.alternatives(part))
+                .map(|name| {
+                    let input = Arc::clone(&input);
+                    let m = run::with_timeout(day, part, timeout, move || {
+                        measure(day, part, || {
+                            run::catch_panic(|| match name {
+                                MAIN => solution.run_part(&input, part),
+                                _ => solution.run_alternative(&input, part, name),
+                            })
+                        })
+                    });
+                    (name, m)
+                })
+                .collect();
+            CrossCheck { day, part, runs }
+        })
+        .collect())
+}
+
+/// A line per implementation, with its time relative to the main one's.
+pub fn format(check: &CrossCheck) -> String {
+    let verdict = if !check.agrees() {
+        "DISAGREEMENT".to_string()
+    } else if !check.is_ok() {
+        "FAILED".to_string()
+    } else {
+        format!("{} implementations agree", check.runs.len())
+    };
+    let main_time = check
+        .runs
+        .first()
+        .filter(|(_, m)| m.is_ok())
+        .map(|(_, m)| m.duration);
+    let width = check
+        .runs
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut text = format!("Day {} {}: {}", check.day, check.part, verdict);
+    for (name, m) in &check.runs {
+        let relative = match main_time {
+            Some(main) if m.is_ok() && !main.is_zero() => {
+                format!("{:.2}x", m.duration.as_secs_f64() / main.as_secs_f64())
+            }
+            _ => "-".to_string(),
+        };
+        let outcome = match (m.status, &m.result, &m.error) {
+            (Status::TimedOut, _, _) => "timed out".to_string(),
+            (_, Some(result), _) => format!("result: {}", result),
+            (_, None, Some(error)) => format!("failed: {}", error),
+            (_, None, None) => "failed".to_string(),
+        };
+        text.push_str(&format!(
+            "\n  {:<width$}  {:>10}  {:>8}  {}",
+            name,
+            format!("{:.2?}", m.duration),
+            relative,
+            outcome,
+            width = width
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use common::rand::rngs::StdRng;
+    use common::{Alternative, ParseError, Solution};
+    use std::path::Path;
+
+    fn run(name: &'static str, result: &str, millis: u64) -> (&'static str, Measurement) {
+        let duration = Duration::from_millis(millis);
+        (
+            name,
+            Measurement::ok(5, Part::Two, result.to_string(), duration),
+        )
+    }
+
+    #[test]
+    fn test_format() {
+        let check = CrossCheck {
+            day: 5,
+            part: Part::Two,
+            runs: vec![run(MAIN, "14", 10), run("sorted merge", "14", 2)],
+        };
+        assert!(check.is_ok());
+        assert_eq!(
+            format(&check),
+            "Day 5 Part 2: 2 implementations agree\n\
+             \x20 main             10.00ms     1.00x  result: 14\n\
+             \x20 sorted merge      2.00ms     0.20x  result: 14"
+        );
+
+        let check = CrossCheck {
+            day: 5,
+            part: Part::Two,
+            runs: vec![run(MAIN, "14", 10), run("sorted merge", "15", 2)],
+        };
+        assert!(!check.agrees());
+        assert!(format(&check).starts_with("Day 5 Part 2: DISAGREEMENT\n"));
+    }
+
+    struct Clash;
+
+    impl Solution for Clash {
+        const DAY: u8 = 42;
+        const TITLE: &'static str = "Clash";
+        const INPUT_SIZE: usize = 1;
+
+        type Input = ();
+        type Output1 = u8;
+        type Output2 = u8;
+
+        const ALTERNATIVES_2: &'static [Alternative<(), u8>] =
+            &[Alternative { name: "main", run: |_| Ok(0) }];
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+        fn part_1(_input: &()) -> Result<u8, Box<dyn Error>> {
+            Ok(0)
+        }
+        fn part_2(_input: &()) -> Result<u8, Box<dyn Error>> {
+            Ok(0)
+        }
+        fn generate(_rng: &mut StdRng, _size: usize) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn test_alternative_named_main() {
+        let error = cross_check_day(&Clash, &InputSource::Stdin, &[Part::Two], None)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("named `main`"), "{}", error);
+        for year in registry::YEARS {
+            for solution in year.solutions {
+                for part in [Part::One, Part::Two] {
+                    assert!(!solution.alternatives(part).contains(&MAIN));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_day_10() {
+        let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../day_10/examples/ex1.txt");
+        let solution = registry::find(2025, 10).unwrap();
+        let checks =
+            cross_check_day(solution, &InputSource::File(example), &[Part::Two], None).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].is_ok(), "{}", format(&checks[0]));
+        assert_eq!(checks[0].runs[0].1.result.as_deref(), Some("33"));
+    }
+}
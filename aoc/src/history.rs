@@ -0,0 +1,321 @@
+use common::Measurement;
+use common::itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default name of the history file, in the workspace root.
+pub const FILE_NAME: &str = "bench_history.jsonl";
+
+/// Benchmark of one part, as stored in the history file (one JSON object per line).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// Start of the run, in milliseconds since the Unix epoch
+    pub timestamp_ms: u128,
+    /// Short hash of the checked out commit, with a `-dirty` suffix if there were changes
+    pub commit: String,
+    pub machine: String,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// [input_hash] of the benchmarked input, since only runs on the same input are comparable
+    pub input: String,
+    pub samples: usize,
+    pub median_ns: u128,
+    pub min_ns: u128,
+}
+
+impl HistoryRecord {
+    /// Records of the same run share these
+    fn run_key(&self) -> (u128, &str, &str) {
+        (self.timestamp_ms, &self.commit, &self.machine)
+    }
+}
+
+/// Short hash of an input's content, the same on every machine and Rust version (64-bit FNV-1a).
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// The benchmarked measurements, as records of a new run of the current commit on this machine.
+/// `inputs` gives the [input_hash] of each day's input; the days without one are left out.
+pub fn new_run(
+    workspace: &Path,
+    year: u16,
+    measurements: &[Measurement],
+    inputs: impl Fn(u8) -> Option<String>,
+) -> Vec<HistoryRecord> {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let commit = current_commit(workspace);
+    let machine = machine_name();
+    measurements
+        .iter()
+        .filter(|m| m.is_ok())
+        .filter_map(|m| {
+            let stats = m.stats?;
+            Some(HistoryRecord {
+                input: inputs(m.day)?,
+                timestamp_ms,
+                commit: commit.clone(),
+                machine: machine.clone(),
+                year,
+                day: m.day,
+                part: m.part.into(),
+                samples: stats.samples,
+                median_ns: stats.median.as_nanos(),
+                min_ns: stats.min.as_nanos(),
+            })
+        })
+        .collect()
+}
+
+pub fn append(path: &Path, records: &[HistoryRecord]) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<Vec<HistoryRecord>, Box<dyn Error>> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e).into())
+        })
+        .collect()
+}
+
+/// Change of the median time of a part since the most recent earlier run which benchmarked it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub latest: Duration,
+    /// None if no earlier run benchmarked the part
+    pub baseline: Option<Baseline>,
+    pub regressed: bool,
+}
+
+/// The median time of a part in an earlier run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Baseline {
+    pub commit: String,
+    pub median: Duration,
+    /// Relative change since then, in percent (positive when slower)
+    pub change: f64,
+}
+
+/// Compares each part of the latest run on `machine` with the most recent earlier run which
+/// benchmarked it too on the same input, e.g. before a run of a single day, flagging the parts which got slower by
+/// more than `threshold` percent. Returns the commit of the latest run too.
+pub fn compare(
+    records: &[HistoryRecord],
+    machine: &str,
+    threshold: f64,
+) -> Result<(String, Vec<Comparison>), Box<dyn Error>> {
+    let runs = records
+        .iter()
+        .filter(|r| r.machine == machine)
+        .chunk_by(|r| r.run_key())
+        .into_iter()
+        .map(|(_, run)| run.collect_vec())
+        .collect_vec();
+    let [.., _, latest] = runs.as_slice() else {
+        return Err(format!("Less than two benchmark runs on {} to compare", machine).into());
+    };
+    let earlier = &runs[..runs.len() - 1];
+    let comparisons = latest
+        .iter()
+        .map(|new| {
+            let key = (new.year, new.day, new.part, &new.input);
+            let old = earlier.iter().rev().find_map(|run| {
+                run.iter()
+                    .find(|old| (old.year, old.day, old.part, &old.input) == key)
+            });
+            let baseline = old.map(|old| Baseline {
+                commit: old.commit.clone(),
+                median: nanos(old.median_ns),
+                change: (new.median_ns as f64 / old.median_ns.max(1) as f64 - 1.0) * 100.0,
+            });
+            Comparison {
+                year: new.year,
+                day: new.day,
+                part: new.part,
+                latest: nanos(new.median_ns),
+                regressed: baseline.as_ref().is_some_and(|b| b.change > threshold),
+                baseline,
+            }
+        })
+        .collect();
+    Ok((latest[0].commit.clone(), comparisons))
+}
+
+fn nanos(ns: u128) -> Duration {
+    Duration::from_nanos(ns.try_into().unwrap_or(u64::MAX))
+}
+
+pub fn format_comparison(c: &Comparison) -> String {
+    let part = format!("{} Day {:>2} Part {}", c.year, c.day, c.part);
+    match &c.baseline {
+        Some(b) => format!(
+            "{}: {:>10.2?} -> {:>10.2?} ({:+.1}% since {}){}",
+            part,
+            b.median,
+            c.latest,
+            b.change,
+            b.commit,
+            if c.regressed { "  REGRESSION" } else { "" }
+        ),
+        None => format!("{}: {:>10} -> {:>10.2?} (no baseline)", part, "-", c.latest),
+    }
+}
+
+fn current_commit(workspace: &Path) -> String {
+    let git = |args: &[&str]| {
+        process::Command::new("git")
+            .arg("-C")
+            .arg(workspace)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if !changes.is_empty() => format!("{}-dirty", commit),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// The host name, which is enough to tell apart the machines of a few people.
+pub fn machine_name() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            process::Command::new("hostname")
+                .output()
+                .ok()
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(timestamp_ms: u128, machine: &str, day: u8, median_ns: u128) -> HistoryRecord {
+        HistoryRecord {
+            timestamp_ms,
+            commit: format!("c{}", timestamp_ms),
+            machine: machine.to_string(),
+            year: 2025,
+            day,
+            part: 1,
+            input: "real".to_string(),
+            samples: 10,
+            median_ns,
+            min_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let records = [
+            record(1, "a", 1, 1000),
+            record(1, "a", 2, 1000),
+            record(2, "b", 1, 5000),
+            record(3, "a", 1, 1050),
+            record(3, "a", 2, 1200),
+            record(3, "a", 3, 1000),
+        ];
+        let (latest, comparisons) = compare(&records, "a", 10.0).unwrap();
+        assert_eq!(latest, "c3");
+        assert_eq!(
+            comparisons
+                .iter()
+                .map(|c| (c.day, c.baseline.is_some(), c.regressed))
+                .collect_vec(),
+            [(1, true, false), (2, true, true), (3, false, false)]
+        );
+        assert_eq!(
+            format_comparison(&comparisons[2]),
+            "2025 Day  3 Part 1:          - ->     1.00µs (no baseline)"
+        );
+        assert!(compare(&records, "b", 10.0).is_err());
+    }
+
+    #[test]
+    fn test_compare_after_partial_run() {
+        let records = [
+            record(1, "a", 1, 1000),
+            record(1, "a", 2, 1000),
+            record(2, "a", 2, 1000),
+            record(3, "a", 1, 2000),
+            record(3, "a", 2, 1000),
+        ];
+        let (_, comparisons) = compare(&records, "a", 10.0).unwrap();
+        let baselines = comparisons
+            .iter()
+            .map(|c| {
+                (
+                    c.day,
+                    c.baseline.as_ref().unwrap().commit.as_str(),
+                    c.regressed,
+                )
+            })
+            .collect_vec();
+        assert_eq!(baselines, [(1, "c1", true), (2, "c2", false)]);
+
+        assert_eq!(
+            format_comparison(&comparisons[0]),
+            "2025 Day  1 Part 1:     1.00µs ->     2.00µs (+100.0% since c1)  REGRESSION"
+        );
+
+        // A run on another input, e.g. a generated one, doesn't compare with the others
+        let mut stress = record(4, "a", 1, 9000);
+        stress.input = "stress".to_string();
+        let records = [&records[..], &[stress]].concat();
+        let (_, comparisons) = compare(&records, "a", 10.0).unwrap();
+        assert!(comparisons[0].baseline.is_none());
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash("1\n2\n"), input_hash("2\n1\n"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", process::id()));
+        let records = [record(1, "a", 1, 1000), record(2, "a", 1, 900)];
+        append(&path, &records[..1]).unwrap();
+        append(&path, &records[1..]).unwrap();
+        assert_eq!(load(&path).unwrap(), records);
+        fs::remove_file(&path).unwrap();
+    }
+}
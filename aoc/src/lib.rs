@@ -0,0 +1,13 @@
+//! The registry of all the days, and the building blocks of the `aoc` runner and server.
+
+pub mod config;
+pub mod cross_check;
+pub mod history;
+pub mod pool;
+pub mod progress_line;
+pub mod readme;
+pub mod registry;
+pub mod repl;
+pub mod run;
+pub mod scaffold;
+pub mod server;
@@ -0,0 +1,596 @@
+use aoc::config::Config;
+use aoc::registry::Year;
+use aoc::run::{CheckMode, RunOptions};
+use aoc::{config, cross_check, history, pool, readme, registry, repl, run, scaffold};
+use clap::{Parser, Subcommand};
+use common::{
+    AnySolution, BenchConfig, CountingAllocator, InputSource, Measurement, OutputFormat, Part,
+};
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Only counts when enabled with --alloc
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs the Advent of Code solutions. Defaults for the options can be set in `aoc.toml` (or the
+/// file from $AOC_CONFIG), see the README.
+#[derive(Parser)]
+#[command(
+    name = "aoc",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Day to run (e.g. `7`) or `all`, optionally after the year (e.g. `2025 7`) [default year:
+    /// the latest one]. A year alone runs all its days
+    #[arg(required = true, num_args = 1..=2, value_name = "[YEAR] DAYS")]
+    days: Vec<String>,
+    /// Only run this part (1 or 2)
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Read the input from this file (`-` for stdin) instead of the input directory; requires a
+    /// single day
+    #[arg(short, long, conflicts_with = "input_dir")]
+    input: Option<String>,
+    /// Directory containing the inputs as `day_N/input` [default: $AOC_INPUT_DIR, or else the
+    /// configured one, or else the workspace root]
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+    /// Output format for the results: human, json (one object per line) or csv [default: the
+    /// configured one, or else human]
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+    /// Benchmark each part: warm up, then run it repeatedly and report timing statistics
+    #[arg(short, long)]
+    bench: bool,
+    /// Number of timed runs per part when benchmarking [default: 10]
+    #[arg(long, requires = "bench", conflicts_with = "bench_time")]
+    runs: Option<u32>,
+    /// Benchmark each part for this many seconds instead of a fixed number of runs
+    #[arg(long, requires = "bench", value_parser = config::parse_seconds)]
+    bench_time: Option<Duration>,
+    /// Number of untimed warm-up runs per part when benchmarking [default: 1]
+    #[arg(long, requires = "bench")]
+    warmup: Option<u32>,
+    /// Append the benchmarks to this history file [default: `bench_history.jsonl` in the
+    /// workspace root]
+    #[arg(long, requires = "bench")]
+    history: Option<PathBuf>,
+    /// Don't append the benchmarks to the history file
+    #[arg(long, requires = "bench", conflicts_with = "history")]
+    no_history: bool,
+    /// Compare the results with the known answers in `day_N/answers.toml`
+    #[arg(short, long)]
+    check: bool,
+    /// Like --check, but also store the results of the parts which have no known answer yet
+    #[arg(long, conflicts_with = "check")]
+    record: bool,
+    /// Stop each part after this many seconds (benchmark included), and report it as timed out
+    #[arg(short, long, value_parser = config::parse_seconds)]
+    timeout: Option<Duration>,
+    /// Override a parameter of the selected days, e.g. `--param connections=10` for day 8
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = config::parse_param)]
+    params: Vec<(String, String)>,
+    /// Count the allocations of each part: number, total size, and peak memory
+    #[arg(long, conflicts_with = "jobs")]
+    alloc: bool,
+    /// Run the days concurrently on this many threads [default: the number of CPUs], and print
+    /// a summary table once they are all done
+    #[arg(short, long, num_args = 0..=1, default_missing_value = "0")]
+    jobs: Option<usize>,
+    /// Run all the implementations of the parts which have alternatives on the same input, and
+    /// report any disagreement along with their relative timings
+    #[arg(long, conflicts_with_all = ["format", "bench", "check", "record", "alloc", "jobs"])]
+    cross_check: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the `day_N` crate from the template, and register it in the workspace and the runner
+    New {
+        day: u8,
+        /// The puzzle's title [default: "Day N"]
+        #[arg(long)]
+        title: Option<String>,
+        /// Workspace to create the day in [default: the workspace of this runner]
+        #[arg(long)]
+        workspace: Option<PathBuf>,
+    },
+    /// Run all the days, checking the known answers, and update the results table in the README
+    Readme {
+        /// Year to run [default: the latest one]
+        #[arg(long)]
+        year: Option<u16>,
+        /// README to update [default: the README of this runner's workspace]
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Directory containing the inputs and known answers [default: $AOC_INPUT_DIR, or else
+        /// the workspace root]
+        #[arg(long)]
+        input_dir: Option<PathBuf>,
+        /// Report the median time of a benchmark instead of a single run
+        #[arg(short, long)]
+        bench: bool,
+        /// Stop each part after this many seconds
+        #[arg(short, long, value_parser = config::parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Check the inputs, reporting all their problems, without solving them
+    Validate {
+        /// Day to check (e.g. `7`) or `all`, optionally after the year (e.g. `2025 7`)
+        #[arg(required = true, num_args = 1..=2, value_name = "[YEAR] DAYS")]
+        days: Vec<String>,
+        /// Directory containing the inputs as `day_N/input` [default: $AOC_INPUT_DIR, or else
+        /// the workspace root]
+        #[arg(long)]
+        input_dir: Option<PathBuf>,
+    },
+    /// Load a day's input, and explore it with commands like `stats`, `show 10..20`, `part2` or
+    /// `time part2 x10` (type `help` for all of them, including the day's own queries)
+    Repl {
+        /// Day to explore (e.g. `7`), optionally after the year (e.g. `2025 7`)
+        #[arg(required = true, num_args = 1..=2, value_name = "[YEAR] DAY")]
+        day: Vec<String>,
+        /// Read the input from this file instead of the input directory
+        #[arg(short, long, conflicts_with = "input_dir")]
+        input: Option<PathBuf>,
+        /// Directory containing the inputs as `day_N/input` [default: $AOC_INPUT_DIR, or else
+        /// the workspace root]
+        #[arg(long)]
+        input_dir: Option<PathBuf>,
+    },
+    /// Generate a random input for a day, e.g. to stress-test or benchmark it at a larger scale
+    Generate {
+        /// Day to generate an input for (e.g. `7`), optionally after the year (e.g. `2025 7`)
+        #[arg(required = true, num_args = 1..=2, value_name = "[YEAR] DAY")]
+        day: Vec<String>,
+        /// Seed of the generator: the same seed and size always give the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Size of the input, whose unit depends on the day (lines, grid side...) [default: the
+        /// size of the real inputs]
+        #[arg(long, conflicts_with = "scale")]
+        size: Option<usize>,
+        /// Size of the input relative to the real inputs, e.g. `100`
+        #[arg(long)]
+        scale: Option<f64>,
+        /// Write the input to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Compare each part of the latest benchmark run on this machine with the most recent earlier
+    /// run of it, and fail if a part got slower
+    Compare {
+        /// Slowdown of the median time above which a part is flagged, in percent
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// History file [default: `bench_history.jsonl` in the workspace root]
+        #[arg(long)]
+        history: Option<PathBuf>,
+        /// Compare the runs of this machine instead
+        #[arg(long)]
+        machine: Option<String>,
+    },
+}
+
+impl Cli {
+    fn bench_config(&self, config: &Config) -> Option<BenchConfig> {
+        self.bench
+            .then(|| config.bench_config(self.runs, self.bench_time, self.warmup))
+    }
+
+    fn check_mode(&self) -> CheckMode {
+        match (self.check, self.record) {
+            (_, true) => CheckMode::Record,
+            (true, false) => CheckMode::Check,
+            (false, false) => CheckMode::Off,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Days {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Days, String> {
+        if s == "all" {
+            Ok(Days::All)
+        } else {
+            s.parse()
+                .map(Days::Day)
+                .map_err(|_| format!("{:?} is neither a day nor `all`", s))
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    common::init_logging();
+    let config = Config::load_default()?;
+
+    match cli.command {
+        Some(Command::New { day, title, workspace }) => {
+            let workspace = workspace.unwrap_or_else(scaffold::default_workspace);
+            let title = title.unwrap_or_else(|| format!("Day {}", day));
+            scaffold::new_day(&workspace, day, &title)?;
+            println!(
+                "Created {}",
+                workspace.join(format!("day_{}", day)).display()
+            );
+            Ok(())
+        }
+        Some(Command::Readme { year, file, input_dir, bench, timeout }) => {
+            let year = match year {
+                Some(year) => registry::year(year).ok_or(format!("No solutions for {}", year))?,
+                None => registry::latest(),
+            };
+            let options = RunOptions {
+                parts: Part::ALL.to_vec(),
+                bench: bench.then(|| config.bench_config(None, None, None)),
+                check: CheckMode::Check,
+                format: OutputFormat::Human,
+                timeout: config.timeout(timeout),
+                progress: io::stderr().is_terminal(),
+            };
+            config.apply_params(year, &[], &[])?;
+            let source = year.input_source(&config.input_source(input_dir));
+            let mut measurements = Vec::new();
+            for solution in year.solutions {
+                eprintln!("Running day {}", solution.day());
+                measurements.extend(run::run_day(*solution, &source, &options, |_| {})?);
+            }
+            let file = file.unwrap_or_else(|| scaffold::default_workspace().join("README.md"));
+            readme::update(&file, &readme::results_table(year.solutions, &measurements))?;
+            println!("Updated {}", file.display());
+            Ok(())
+        }
+        Some(Command::Validate { days, input_dir }) => {
+            let (year, days) = parse_selection(&days)?;
+            let source = year.input_source(&config.input_source(input_dir));
+            let mut invalid = 0;
+            for solution in select(year, days)? {
+                let result = source
+                    .read(solution.day())
+                    .and_then(|input| Ok(solution.validate_input(&input)?));
+                match result {
+                    Ok(()) => println!("Day {}: ok", solution.day()),
+                    Err(e) => {
+                        println!("Day {}: {}", solution.day(), e);
+                        invalid += 1;
+                    }
+                }
+            }
+            if invalid > 0 {
+                Err(format!("{} invalid input(s)", invalid).into())
+            } else {
+                Ok(())
+            }
+        }
+        Some(Command::Repl { day, input, input_dir }) => {
+            let (year, solution) = select_day(&day)?;
+            config.apply_params(year, &[], &[])?;
+            // Not from stdin, which is where the commands come from
+            let source = match input {
+                Some(path) => InputSource::File(path),
+                None => config.input_source(input_dir),
+            };
+            let text = year.input_source(&source).read(solution.day())?;
+            let stdin = io::stdin();
+            let prompt = stdin.is_terminal();
+            repl::run_repl(solution, &text, stdin.lock(), io::stdout(), prompt)
+        }
+        Some(Command::Generate { day, seed, size, scale, output }) => {
+            let (_, solution) = select_day(&day)?;
+            let size = size.unwrap_or_else(|| {
+                (solution.input_size() as f64 * scale.unwrap_or(1.0)).round() as usize
+            });
+            let input = solution.generate_input(seed, size);
+            match output {
+                Some(path) => {
+                    fs::write(&path, input)
+                        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+                    eprintln!("Generated {}", path.display());
+                }
+                None => print!("{}", input),
+            }
+            Ok(())
+        }
+        Some(Command::Compare { threshold, history, machine }) => {
+            let path =
+                history.unwrap_or_else(|| scaffold::default_workspace().join(history::FILE_NAME));
+            let machine = machine.unwrap_or_else(history::machine_name);
+            let records = history::load(&path)?;
+            let (latest, comparisons) = history::compare(&records, &machine, threshold)?;
+            println!("Comparing {} with the earlier runs on {}", latest, machine);
+            for comparison in &comparisons {
+                println!("{}", history::format_comparison(comparison));
+            }
+            let regressions = comparisons.iter().filter(|c| c.regressed).count();
+            if regressions > 0 {
+                Err(format!(
+                    "{} part(s) regressed by more than {}%",
+                    regressions, threshold
+                )
+                .into())
+            } else {
+                Ok(())
+            }
+        }
+        None => run_days(cli, &config),
+    }
+}
+
+/// The year and days of `[YEAR] DAYS` arguments. A year alone means all its days, and the days
+/// alone are those of the latest year.
+fn parse_selection(args: &[String]) -> Result<(&'static Year, Days), Box<dyn Error>> {
+    match args {
+        [days] => match days.parse().ok().and_then(registry::year) {
+            Some(year) => Ok((year, Days::All)),
+            None => Ok((registry::latest(), days.parse()?)),
+        },
+        [year, days] => {
+            let year = year
+                .parse()
+                .map_err(|_| format!("{:?} is not a year", year))?;
+            let year = registry::year(year).ok_or(format!("No solutions for {}", year))?;
+            Ok((year, days.parse()?))
+        }
+        _ => Err("Expected a day or `all`, optionally after a year".into()),
+    }
+}
+
+fn select(year: &Year, days: Days) -> Result<Vec<&'static dyn AnySolution>, Box<dyn Error>> {
+    match days {
+        Days::All => Ok(year.solutions.to_vec()),
+        Days::Day(day) => {
+            Ok(vec![year.find(day).ok_or(format!(
+                "No solution for day {} of {}",
+                day, year.year
+            ))?])
+        }
+    }
+}
+
+/// The year and solution of `[YEAR] DAY` arguments.
+fn select_day(
+    args: &[String],
+) -> Result<(&'static Year, &'static dyn AnySolution), Box<dyn Error>> {
+    match parse_selection(args)? {
+        (year, Days::Day(day)) => Ok((year, select(year, Days::Day(day))?[0])),
+        (_, Days::All) => Err("Expected a single day".into()),
+    }
+}
+
+fn run_days(cli: Cli, config: &Config) -> Result<(), Box<dyn Error>> {
+    let (year, days) = parse_selection(&cli.days)?;
+    let solutions = select(year, days)?;
+    let source = match &cli.input {
+        Some(_) if solutions.len() > 1 => return Err("--input requires a single day".into()),
+        Some(path) => InputSource::from_arg(path),
+        None => config.input_source(cli.input_dir.clone()),
+    };
+    let selected = solutions.iter().map(|s| s.day()).collect::<Vec<_>>();
+    config.apply_params(year, &selected, &cli.params)?;
+    let source = year.input_source(&source);
+    let format = cli.format.or(config.format).unwrap_or_default();
+    let options = RunOptions {
+        parts: match cli.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        },
+        bench: cli.bench_config(config),
+        check: cli.check_mode(),
+        format,
+        timeout: config.timeout(cli.timeout),
+        // Only for humans, and only if the lines can be overwritten
+        progress: format == OutputFormat::Human && cli.jobs.is_none() && io::stderr().is_terminal(),
+    };
+
+    if options.check != CheckMode::Off && !matches!(source, InputSource::Dir(_)) {
+        return Err("--check and --record require an input directory, not --input".into());
+    }
+
+    if cli.cross_check {
+        return run_cross_check(&solutions, &source, &options);
+    }
+    if cli.alloc {
+        common::enable_alloc_counting()?;
+    }
+    if let Some(header) = options.format.header() {
+        println!("{}", header);
+    }
+    let measurements = match cli.jobs {
+        None => run_sequential(&solutions, &source, &options),
+        Some(jobs) => run_parallel(&solutions, &source, &options, jobs),
+    };
+
+    if cli.bench && !cli.no_history {
+        let workspace = scaffold::default_workspace();
+        let path = cli
+            .history
+            .unwrap_or_else(|| workspace.join(history::FILE_NAME));
+        // Stdin can't be read again, so its benchmarks are not recorded
+        let inputs = |day| {
+            source
+                .path(day)
+                .and_then(|_| source.read(day).ok())
+                .map(|input| history::input_hash(&input))
+        };
+        let records = history::new_run(&workspace, year.year, &measurements, inputs);
+        history::append(&path, &records)?;
+        eprintln!("Appended the benchmarks to {}", path.display());
+    }
+
+    let failures = measurements.iter().filter(|m| !m.is_ok()).count();
+
+    if failures > 0 {
+        Err(format!("{} part(s) failed or gave a wrong answer", failures).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn run_cross_check(
+    solutions: &[&'static dyn AnySolution],
+    source: &InputSource,
+    options: &RunOptions,
+) -> Result<(), Box<dyn Error>> {
+    let mut checked = 0;
+    let mut failures = 0;
+    for solution in solutions {
+        let checks =
+            cross_check::cross_check_day(*solution, source, &options.parts, options.timeout)
+                .map_err(|e| format!("Day {} failed: {}", solution.day(), e))?;
+        for check in &checks {
+            println!("{}", cross_check::format(check));
+            checked += 1;
+            if !check.is_ok() {
+                failures += 1;
+            }
+        }
+    }
+    if checked == 0 {
+        Err("None of the selected parts has alternative implementations".into())
+    } else if failures > 0 {
+        Err(format!("{} part(s) disagreed or failed", failures).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Prints the measurements as they come, and returns them.
+fn run_sequential(
+    solutions: &[&'static dyn AnySolution],
+    source: &InputSource,
+    options: &RunOptions,
+) -> Vec<Measurement> {
+    let mut measurements = Vec::new();
+    for solution in solutions {
+        if options.format == OutputFormat::Human {
+            println!("Day {}", solution.day());
+        }
+        let print = |m: &Measurement| println!("{}", options.format.format(m));
+        let result = run::run_day(*solution, source, options, print);
+        measurements.extend(day_measurements(*solution, options, result));
+    }
+    measurements
+}
+
+/// The measurements of a day, or skipped ones for all its parts if it couldn't run.
+fn day_measurements<E: Display>(
+    solution: &dyn AnySolution,
+    options: &RunOptions,
+    result: Result<Vec<Measurement>, E>,
+) -> Vec<Measurement> {
+    result.unwrap_or_else(|e| {
+        eprintln!("Day {} failed: {}", solution.day(), e);
+        options
+            .parts
+            .iter()
+            .map(|&part| Measurement::skipped(solution.day(), part))
+            .collect()
+    })
+}
+
+/// Runs one day per job, then prints all the measurements sorted by day, and returns them.
+fn run_parallel(
+    solutions: &[&'static dyn AnySolution],
+    source: &InputSource,
+    options: &RunOptions,
+    jobs: usize,
+) -> Vec<Measurement> {
+    let threads = match jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let before = Instant::now();
+    let cpu_before = pool::cpu_time();
+    let results = pool::map(solutions, threads, |solution| {
+        run::run_day(*solution, source, options, |_| {}).map_err(|e| e.to_string())
+    });
+    let wall_time = before.elapsed();
+    let cpu_time = pool::cpu_time()
+        .zip(cpu_before)
+        .map(|(after, before)| after.saturating_sub(before));
+
+    let mut measurements = solutions
+        .iter()
+        .zip(results)
+        .flat_map(|(solution, result)| day_measurements(*solution, options, result))
+        .collect::<Vec<_>>();
+    measurements.sort_by_key(|m| (m.day, m.part));
+
+    match options.format {
+        OutputFormat::Human => {
+            print!("{}", summary_table(&measurements));
+            for m in measurements.iter().filter(|m| !m.is_ok()) {
+                println!("\nDay {} {}", m.day, options.format.format(m));
+            }
+            let cpu_time = cpu_time.map_or("unknown".to_string(), |t| format!("{:.2?}", t));
+            println!(
+                "\nWall-clock time: {:.2?}, CPU time: {} ({} threads)",
+                wall_time,
+                cpu_time,
+                threads.min(solutions.len())
+            );
+        }
+        OutputFormat::Json | OutputFormat::Csv => {
+            for m in &measurements {
+                println!("{}", options.format.format(m));
+            }
+        }
+    }
+    measurements
+}
+
+/// One row per part, with the columns aligned.
+fn summary_table(measurements: &[Measurement]) -> String {
+    let header = ["Day", "Part", "Answer", "Time", "Status"].map(String::from);
+    let rows = measurements
+        .iter()
+        .map(|m| {
+            [
+                m.day.to_string(),
+                u8::from(m.part).to_string(),
+                m.result.clone().unwrap_or_default(),
+                format!("{:.2?}", m.duration),
+                m.status.as_str().replace('_', " "),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let widths = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let mut table = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let cells = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
@@ -0,0 +1,87 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Applies `f` to all the items on a pool of `threads` worker threads, and returns the results in
+/// the same order as the items. Workers pick the next pending item as soon as they are done with
+/// the previous one, so slow items don't hold back the others.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    let result = f(item);
+                    results.lock().unwrap()[i] = Some(result);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("All items are processed"))
+        .collect()
+}
+
+/// CPU time used by the whole process so far, user and system, on all its threads.
+#[cfg(unix)]
+pub fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: `getrusage` fills in `usage` when it succeeds
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let usage = unsafe { usage.assume_init() };
+    let duration = |t: libc::timeval| -> Option<Duration> {
+        Some(
+            Duration::from_secs(t.tv_sec.try_into().ok()?)
+                + Duration::from_micros(t.tv_usec.try_into().ok()?),
+        )
+    };
+    Some(duration(usage.ru_utime)? + duration(usage.ru_stime)?)
+}
+
+/// Unknown on other platforms.
+#[cfg(not(unix))]
+pub fn cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        assert_eq!(
+            map(&items, 8, |x| x * 2),
+            items.iter().map(|x| x * 2).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&[] as &[u64], 8, |x| *x), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_cpu_time() {
+        let before = cpu_time().unwrap();
+        let items = (0..4).collect::<Vec<u64>>();
+        let sums = map(&items, 4, |&x| {
+            (0..20_000_000u64).fold(x, |a, b| a ^ b.wrapping_mul(a | 1))
+        });
+        assert_eq!(sums.len(), 4);
+        assert!(cpu_time().unwrap() > before);
+    }
+}
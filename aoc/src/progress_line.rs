@@ -0,0 +1,70 @@
+use common::progress::{ProgressSink, ProgressState};
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum delay between redraws, and before the first one so that quick parts don't flicker.
+const REDRAW_DELAY: Duration = Duration::from_millis(100);
+
+/// Live progress line on stderr, like `Day 10 Part 2: 45/120 (37%) - 7 buttons`.
+pub struct ProgressLine {
+    label: String,
+    /// When the line was last drawn, and whether it was drawn at all
+    last_draw: Mutex<(Instant, bool)>,
+}
+
+impl ProgressLine {
+    pub fn new(label: String) -> ProgressLine {
+        ProgressLine { label, last_draw: Mutex::new((Instant::now(), false)) }
+    }
+
+    /// Erases the line, if it was drawn.
+    pub fn finish(&self) {
+        if self.last_draw.lock().unwrap().1 {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+impl ProgressSink for ProgressLine {
+    fn update(&self, state: &ProgressState) {
+        let mut last_draw = self.last_draw.lock().unwrap();
+        if last_draw.0.elapsed() < REDRAW_DELAY {
+            return;
+        }
+        *last_draw = (Instant::now(), true);
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{}", render(&self.label, state));
+        let _ = stderr.flush();
+    }
+}
+
+fn render(label: &str, state: &ProgressState) -> String {
+    let count = match state.total {
+        Some(total) if total > 0 => {
+            format!("{}/{} ({}%)", state.done, total, state.done * 100 / total)
+        }
+        _ => state.done.to_string(),
+    };
+    match &state.message {
+        Some(message) => format!("{}: {} - {}", label, count, message),
+        None => format!("{}: {}", label, count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut state = ProgressState { total: Some(120), done: 45, message: None };
+        assert_eq!(
+            render("Day 10 Part 2", &state),
+            "Day 10 Part 2: 45/120 (37%)"
+        );
+        state.total = None;
+        state.message = Some("round 3".to_string());
+        assert_eq!(render("Day 4 Part 2", &state), "Day 4 Part 2: 45 - round 3");
+    }
+}
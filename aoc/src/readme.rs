@@ -0,0 +1,109 @@
+use common::itertools::Itertools;
+use common::{AnySolution, Measurement, Part, Status};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+const START_MARKER: &str = "<!-- results:start -->";
+const END_MARKER: &str = "<!-- results:end -->";
+
+/// Markdown table of the days' timings, with a checkmark for the results matching a known answer.
+/// The answers themselves are left out.
+pub fn results_table(solutions: &[&dyn AnySolution], measurements: &[Measurement]) -> String {
+    let mut table =
+        String::from("| Day | Title | Part 1 | Part 2 |\n|----:|-------|-------:|-------:|\n");
+    for solution in solutions {
+        let cells = Part::ALL
+            .iter()
+            .map(|&part| {
+                match measurements
+                    .iter()
+                    .find(|m| m.day == solution.day() && m.part == part)
+                {
+                    Some(m) => result_cell(m),
+                    None => String::new(),
+                }
+            })
+            .join(" | ");
+        table.push_str(&format!(
+            "| {} | {} | {} |\n",
+            solution.day(),
+            solution.title(),
+            cells
+        ));
+    }
+    table
+}
+
+fn result_cell(m: &Measurement) -> String {
+    match (m.status, &m.expected) {
+        (Status::Ok, Some(_)) => format!("{:.2?} ✅", m.duration),
+        (Status::Ok, None) => format!("{:.2?}", m.duration),
+        (Status::Wrong, _) => format!("{:.2?} ❌", m.duration),
+        (Status::Failed, _) => "failed".to_string(),
+        (Status::TimedOut, _) => format!("timed out ({:.2?})", m.duration),
+        (Status::Skipped, _) => "skipped".to_string(),
+    }
+}
+
+/// Replaces the results section of the README with `table`, or appends it if there is none yet.
+pub fn update(path: &Path, table: &str) -> Result<(), Box<dyn Error>> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    fs::write(path, replace_section(&text, table)?)?;
+    Ok(())
+}
+
+fn replace_section(text: &str, table: &str) -> Result<String, Box<dyn Error>> {
+    let section = format!(
+        "{}\n\n_Generated by `cargo run --release -p aoc -- readme`; ✅ marks the answers \
+         matching the known ones._\n\n{}\n{}",
+        START_MARKER, table, END_MARKER
+    );
+    match (text.find(START_MARKER), text.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => Ok(format!(
+            "{}{}{}",
+            &text[..start],
+            section,
+            &text[end + END_MARKER.len()..]
+        )),
+        (None, None) => Ok(format!("{}\n## Results\n\n{}\n", text, section)),
+        _ => Err(format!("Mismatched `{}` and `{}` markers", START_MARKER, END_MARKER).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_results_table() {
+        let mut verified =
+            Measurement::ok(1, Part::One, "1168".to_string(), Duration::from_micros(17));
+        verified.check("1168");
+        let unverified =
+            Measurement::ok(1, Part::Two, "7199".to_string(), Duration::from_millis(2));
+        let table = results_table(&[&day_1::Day1], &[verified, unverified]);
+        assert_eq!(
+            table,
+            "| Day | Title | Part 1 | Part 2 |\n|----:|-------|-------:|-------:|\n\
+             | 1 | Secret Entrance | 17.00µs ✅ | 2.00ms |\n"
+        );
+        assert!(!table.contains("1168"));
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let readme = format!("# AoC\n\n{}\nold\n{}\n\nFooter\n", START_MARKER, END_MARKER);
+        let updated = replace_section(&readme, "new\n").unwrap();
+        assert!(updated.starts_with("# AoC\n\n<!-- results:start -->\n"));
+        assert!(updated.ends_with("new\n\n<!-- results:end -->\n\nFooter\n"));
+        assert!(!updated.contains("old"));
+        assert_eq!(replace_section(&updated, "new\n").unwrap(), updated);
+
+        let appended = replace_section("# AoC\n", "new\n").unwrap();
+        assert!(appended.starts_with("# AoC\n\n## Results\n\n<!-- results:start -->"));
+        assert!(replace_section(END_MARKER, "new\n").is_err());
+    }
+}
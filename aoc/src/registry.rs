@@ -0,0 +1,58 @@
+use common::{AnySolution, InputSource};
+
+/// A calendar: the solutions of a year's puzzles.
+pub struct Year {
+    pub year: u16,
+    /// Directory of the year's `day_N` crates and inputs, relative to the workspace root (and to
+    /// the input directory)
+    pub dir: &'static str,
+    /// All days, in calendar order
+    pub solutions: &'static [&'static dyn AnySolution],
+}
+
+impl Year {
+    pub fn find(&self, day: u8) -> Option<&'static dyn AnySolution> {
+        self.solutions.iter().find(|s| s.day() == day).copied()
+    }
+
+    /// Where this year's inputs are, if `source` is the input directory of all the years.
+    pub fn input_source(&self, source: &InputSource) -> InputSource {
+        match source {
+            InputSource::Dir(dir) if !self.dir.is_empty() => InputSource::Dir(dir.join(self.dir)),
+            _ => source.clone(),
+        }
+    }
+}
+
+/// The days of 2025, whose crates are at the workspace root.
+static AOC_2025: &[&dyn AnySolution] = &[
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_3::Day3,
+    &day_4::Day4,
+    &day_5::Day5,
+    &day_6::Day6,
+    &day_7::Day7,
+    &day_8::Day8,
+    &day_9::Day9,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+];
+
+/// All years, oldest first. The days of other years go in a `<year>` directory, e.g.
+/// `2024/day_7`.
+pub static YEARS: &[Year] = &[Year { year: 2025, dir: "", solutions: AOC_2025 }];
+
+/// The year of the commands which don't specify one.
+pub fn latest() -> &'static Year {
+    YEARS.last().expect("There is at least one year")
+}
+
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static dyn AnySolution> {
+    self::year(year)?.find(day)
+}
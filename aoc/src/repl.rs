@@ -0,0 +1,258 @@
+use crate::run;
+use common::{
+    AnySolution, BenchConfig, Budget, Measurement, OutputFormat, ParsedInput, Part, measure,
+    measure_bench,
+};
+use std::error::Error;
+use std::io::{BufRead, Write};
+use std::time::Instant;
+
+const HELP: &str = "\
+help                 show this help
+stats                statistics about the input
+show <from>..<to>    show the lines of the input in this range, e.g. `10..20` or `10..=20`
+part1, part2         solve a part
+time <part> [x<n>]   benchmark a part over n runs, e.g. `time part2 x10`
+quit                 leave the REPL";
+
+/// Loads the day's input, then answers the commands read from `commands` until `quit` or the end
+/// of the commands. Errors only stop the command which caused them.
+pub fn run_repl(
+    solution: &'static dyn AnySolution,
+    text: &str,
+    commands: impl BufRead,
+    mut out: impl Write,
+    prompt: bool,
+) -> Result<(), Box<dyn Error>> {
+    let before = Instant::now();
+    let input = run::catch_panic(|| solution.parse_input(text))?;
+    writeln!(
+        out,
+        "Day {} ({}): parsed {} lines in {:.2?}, type `help` for the commands",
+        solution.day(),
+        solution.title(),
+        text.lines().count(),
+        before.elapsed()
+    )?;
+
+    let mut commands = commands.lines();
+    loop {
+        if prompt {
+            write!(out, "> ")?;
+            out.flush()?;
+        }
+        let Some(command) = commands.next() else {
+            break;
+        };
+        let command = command?;
+        let words = command.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            [] => continue,
+            ["quit" | "exit"] => break,
+            _ => {}
+        }
+        match execute(solution, text, &input, &words) {
+            Ok(output) => writeln!(out, "{}", output)?,
+            Err(e) => writeln!(out, "Error: {}", e)?,
+        }
+    }
+    Ok(())
+}
+
+fn execute(
+    solution: &'static dyn AnySolution,
+    text: &str,
+    input: &ParsedInput,
+    words: &[&str],
+) -> Result<String, Box<dyn Error>> {
+    match words {
+        ["help"] => {
+            let queries = solution
+                .queries()
+                .iter()
+                .map(|q| {
+                    let usage = format!("{} {}", q.name, q.args);
+                    format!("\n{:<20} {}", usage.trim_end(), q.help)
+                })
+                .collect::<String>();
+            Ok(format!("{}{}", HELP, queries))
+        }
+        ["stats"] => {
+            let stats = [
+                ("lines", text.lines().count().to_string()),
+                ("bytes", text.len().to_string()),
+            ]
+            .into_iter()
+            .chain(solution.stats(input)?);
+            Ok(stats
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        ["show", range] => show(text, range),
+        [part @ ("part1" | "part2")] => {
+            let part = parse_part(part)?;
+            Ok(format_measurement(&measure(solution.day(), part, || {
+                run::catch_panic(|| solution.run_part(input, part))
+            })))
+        }
+        ["time", part, runs @ ..] => {
+            let part = parse_part(part)?;
+            let runs = match runs {
+                [] => 10,
+                [runs] => runs
+                    .strip_prefix('x')
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .ok_or(format!(
+                        "Expected a number of runs like `x10`, found `{}`",
+                        runs
+                    ))?,
+                _ => return Err("Usage: time <part> [x<n>]".into()),
+            };
+            let config = BenchConfig { warmup: 1, budget: Budget::Runs(runs) };
+            Ok(format_measurement(&measure_bench(
+                solution.day(),
+                part,
+                &config,
+                || run::catch_panic(|| solution.run_part(input, part)),
+            )))
+        }
+        [name, args @ ..] if solution.queries().iter().any(|q| q.name == *name) => {
+            solution.query(input, name, args)
+        }
+        [name, ..] => {
+            Err(format!("Unknown command `{}`, type `help` for the commands", name).into())
+        }
+        [] => Ok(String::new()),
+    }
+}
+
+fn parse_part(word: &str) -> Result<Part, String> {
+    match word {
+        "part1" => Ok(Part::One),
+        "part2" => Ok(Part::Two),
+        _ => Err(format!("Expected `part1` or `part2`, found `{}`", word)),
+    }
+}
+
+fn format_measurement(m: &Measurement) -> String {
+    OutputFormat::Human.format(m)
+}
+
+/// The lines of `text` in `range`, numbered from 1, which must contain at least one of them.
+fn show(text: &str, range: &str) -> Result<String, Box<dyn Error>> {
+    let invalid = || format!("Expected a range of lines like `10..20`, found `{}`", range);
+    let (from, to) = match range.split_once("..") {
+        Some((from, to)) => {
+            let from = from.parse::<usize>().map_err(|_| invalid())?;
+            let to = match to.strip_prefix('=') {
+                Some(to) => to.parse::<usize>().ok().and_then(|to| to.checked_add(1)),
+                None => to.parse().ok(),
+            };
+            (from, to.ok_or_else(invalid)?)
+        }
+        None => {
+            let line = range.parse::<usize>().map_err(|_| invalid())?;
+            (line, line.checked_add(1).ok_or_else(invalid)?)
+        }
+    };
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(number, _)| (from..to).contains(number))
+        .collect::<Vec<_>>();
+    let Some(&(last, _)) = lines.last() else {
+        return Err(format!(
+            "No lines in `{}`, the input has lines 1 to {}",
+            range,
+            text.lines().count()
+        )
+        .into());
+    };
+    let width = last.to_string().len();
+    Ok(lines
+        .iter()
+        .map(|(number, line)| format!("{:>width$} | {}", number, line, width = width))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_repl() {
+        let text = include_str!("../../day_1/examples/ex1.txt");
+        let commands = "show 2..=3\n\nstats\npart1\ntime part2 x3\nfoo\nquit\npart2\n";
+        let mut out = Vec::new();
+        run_repl(
+            registry::find(2025, 1).unwrap(),
+            text,
+            commands.as_bytes(),
+            &mut out,
+            false,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("Day 1 (Secret Entrance): parsed 10 lines in "));
+        assert_eq!(
+            lines[1..6],
+            [
+                "2 | L30",
+                "3 | R48",
+                "lines: 10",
+                "bytes: 38",
+                "rotations: 10"
+            ]
+        );
+        assert!(out.contains("Part 1 elapsed time: "));
+        assert!(out.contains("Part 2 median time: ") && out.contains("3 runs), result: 6"));
+        assert!(out.contains("Error: Unknown command `foo`"));
+        assert_eq!(out.matches("result: 6").count(), 1);
+    }
+
+    #[test]
+    fn test_show() {
+        let text = (1..=12)
+            .map(|i| format!("line {}\n", i))
+            .collect::<String>();
+        assert_eq!(show(&text, "11..20").unwrap(), "11 | line 11\n12 | line 12");
+        assert_eq!(show(&text, "3").unwrap(), "3 | line 3");
+        for range in ["1..=18446744073709551615", "18446744073709551615", "x..2"] {
+            assert!(
+                show(&text, range)
+                    .unwrap_err()
+                    .to_string()
+                    .starts_with("Expected")
+            );
+        }
+        for range in ["5..2", "5..5", "13..20", "0"] {
+            assert_eq!(
+                show(&text, range).unwrap_err().to_string(),
+                format!("No lines in `{}`, the input has lines 1 to 12", range)
+            );
+        }
+    }
+
+    #[test]
+    fn test_time_runs() {
+        let text = include_str!("../../day_1/examples/ex1.txt");
+        let mut out = Vec::new();
+        let solution = registry::find(2025, 1).unwrap();
+        run_repl(
+            solution,
+            text,
+            "time part1 x0\n".as_bytes(),
+            &mut out,
+            false,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Error: Expected a number of runs like `x10`, found `x0`"));
+    }
+}
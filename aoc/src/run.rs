@@ -0,0 +1,220 @@
+use crate::progress_line::ProgressLine;
+use common::itertools::Itertools;
+use common::progress;
+use common::{
+    Answers, AnySolution, BenchConfig, CancelToken, InputSource, Measurement, OutputFormat, Part,
+    measure, measure_bench,
+};
+use std::any::Any;
+use std::error::Error;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a timed out part has to notice its cancellation, before it is left running in the
+/// background.
+const CANCELLATION_GRACE: Duration = Duration::from_millis(100);
+
+/// Whether to compare the results with the known answers stored next to the inputs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CheckMode {
+    Off,
+    Check,
+    /// Check the known answers, and store the results for the parts which don't have one yet
+    Record,
+}
+
+pub struct RunOptions {
+    pub parts: Vec<Part>,
+    pub bench: Option<BenchConfig>,
+    pub check: CheckMode,
+    pub format: OutputFormat,
+    /// Maximum duration of each part, benchmark included
+    pub timeout: Option<Duration>,
+    /// Show a live progress line for the parts which report their progress
+    pub progress: bool,
+}
+
+/// Runs the selected parts of a day, passing each measurement to `report` as soon as it is
+/// available. A panicking solution only fails its own part, while an input which can't be read or
+/// parsed is reported once, and its parts are skipped.
+pub fn run_day(
+    solution: &'static dyn AnySolution,
+    source: &InputSource,
+    options: &RunOptions,
+    mut report: impl FnMut(&Measurement),
+) -> Result<Vec<Measurement>, Box<dyn Error>> {
+    let day = solution.day();
+
+    let answers_path = match options.check {
+        CheckMode::Off => None,
+        CheckMode::Check | CheckMode::Record => Some(
+            source
+                .answers_path(day)
+                .ok_or("Known answers are only available for an input directory")?,
+        ),
+    };
+    let mut answers = match &answers_path {
+        Some(path) => Answers::load(path)?,
+        None => Answers::default(),
+    };
+    let mut recorded = false;
+
+    let input = source
+        .read(day)
+        .and_then(|input| catch_panic(|| solution.parse_input(&input)))
+        .map(Arc::new);
+    if let Err(e) = &input {
+        eprintln!("Day {} failed: {}", day, e);
+    }
+    let measurements = options
+        .parts
+        .iter()
+        .map(|&part| {
+            let mut m = match &input {
+                Ok(input) => {
+                    let input = Arc::clone(input);
+                    let bench = options.bench;
+                    let show_progress = options.progress;
+                    with_timeout(day, part, options.timeout, move || {
+                        let run_part = || catch_panic(|| solution.run_part(&input, part));
+                        let measure_part = || match &bench {
+                            Some(config) => measure_bench(day, part, config, run_part),
+                            None => measure(day, part, run_part),
+                        };
+                        if show_progress {
+                            let line = Arc::new(ProgressLine::new(format!("Day {} {}", day, part)));
+                            let m = progress::install(line.clone(), measure_part);
+                            line.finish();
+                            m
+                        } else {
+                            measure_part()
+                        }
+                    })
+                }
+                Err(_) => Measurement::skipped(day, part),
+            };
+            match (options.check, answers.get(part), &m.result) {
+                (CheckMode::Off, _, _) => {}
+                (_, Some(expected), _) => m.check(expected),
+                (CheckMode::Record, None, Some(result)) => {
+                    answers.set(part, result.clone());
+                    recorded = true;
+                }
+                (_, None, _) => eprintln!("Day {} {}: no known answer", day, part),
+            }
+            report(&m);
+            m
+        })
+        .collect_vec();
+
+    if let (true, Some(path)) = (recorded, &answers_path) {
+        answers.save(path)?;
+        eprintln!("Day {}: recorded new answers in {}", day, path.display());
+    }
+
+    Ok(measurements)
+}
+
+/// Runs `f` on another thread with a cancellation token, which is cancelled if `f` takes longer
+/// than the timeout. Then the part is reported as timed out, even if it doesn't stop.
+pub fn with_timeout(
+    day: u8,
+    part: Part,
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> Measurement + Send + 'static,
+) -> Measurement {
+    let Some(timeout) = timeout else {
+        return f();
+    };
+    let token = CancelToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let before = Instant::now();
+    let worker_token = token.clone();
+    thread::spawn(move || {
+        // The receiver is gone if the part timed out
+        let _ = sender.send(worker_token.install(f));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(m) => m,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            token.cancel();
+            let _ = receiver.recv_timeout(CANCELLATION_GRACE);
+            Measurement::timed_out(day, part, timeout)
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Measurement::failed(
+            day,
+            part,
+            "the worker thread died".to_string(),
+            before.elapsed(),
+        ),
+    }
+}
+
+/// Turns a panic into an error, so that one broken solution doesn't bring down the whole run.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T, Box<dyn Error>>) -> Result<T, Box<dyn Error>> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(&payload)).into()))
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use common::{Cancelled, Status, check_cancelled};
+    use std::{fs, process};
+
+    #[test]
+    fn test_with_timeout() {
+        let timeout = Some(Duration::from_millis(10));
+        let m = with_timeout(1, Part::One, timeout, || {
+            measure(1, Part::One, || -> Result<u32, Cancelled> {
+                loop {
+                    check_cancelled()?;
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+        });
+        assert_eq!(m.status, Status::TimedOut);
+        let m = with_timeout(1, Part::Two, timeout, || {
+            Measurement::ok(1, Part::Two, "2".to_string(), Duration::ZERO)
+        });
+        assert_eq!(m.status, Status::Ok);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let path = std::env::temp_dir().join(format!("aoc-run-{}.txt", process::id()));
+        fs::write(&path, "L10\nx5\n").unwrap();
+        let options = RunOptions {
+            parts: vec![Part::One, Part::Two],
+            bench: None,
+            check: CheckMode::Off,
+            format: OutputFormat::Human,
+            timeout: None,
+            progress: false,
+        };
+        let solution = registry::find(2025, 1).unwrap();
+        let mut reported = Vec::new();
+        let measurements = run_day(solution, &InputSource::File(path.clone()), &options, |m| {
+            reported.push(OutputFormat::Human.format(m))
+        })
+        .unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(measurements.iter().all(|m| m.status == Status::Skipped));
+        assert_eq!(reported, ["Part 1 skipped", "Part 2 skipped"]);
+    }
+}
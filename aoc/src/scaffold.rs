@@ -0,0 +1,186 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../template/Cargo.toml.tpl");
+const LIB_RS: &str = include_str!("../template/lib.rs.tpl");
+const MAIN_RS: &str = include_str!("../template/main.rs.tpl");
+const FUZZ_TARGET_RS: &str = include_str!("../template/fuzz_target.rs.tpl");
+
+/// The workspace this runner was built from.
+pub fn default_workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The runner is in a workspace")
+        .to_path_buf()
+}
+
+/// Creates the `day_N` crate from the template, with empty input and example files, and
+/// registers it in the workspace members, the runner's dependencies and the runner's registry.
+/// Also adds a fuzz target for it, if the workspace has a `fuzz` crate.
+pub fn new_day(workspace: &Path, day: u8, title: &str) -> Result<(), Box<dyn Error>> {
+    let dir = workspace.join(format!("day_{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    // Register first, so that nothing is left half-created if a file can't be edited
+    let registrations = [
+        (Path::new("Cargo.toml"), format!("    \"day_{}\",", day)),
+        (
+            Path::new("aoc/Cargo.toml"),
+            format!("day_{0} = {{ path = \"../day_{0}\"}}", day),
+        ),
+        (
+            Path::new("aoc/src/registry.rs"),
+            format!("    &day_{0}::Day{0},", day),
+        ),
+    ]
+    .into_iter()
+    .map(|(file, line)| {
+        let path = workspace.join(file);
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let text = insert_day_line(&text, day, &line)
+            .ok_or_else(|| format!("No `day_N` entries to extend in {}", path.display()))?;
+        Ok((path, text))
+    })
+    .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    let fuzz_manifest = workspace.join("fuzz/Cargo.toml");
+    let fuzz_registration = if fuzz_manifest.exists() {
+        let text = fs::read_to_string(&fuzz_manifest)?;
+        let dependency = format!("day_{0} = {{ path = \"../day_{0}\"}}", day);
+        let text = insert_day_line(&text, day, &dependency).ok_or_else(|| {
+            format!(
+                "No `day_N` entries to extend in {}",
+                fuzz_manifest.display()
+            )
+        })?;
+        let target = format!(
+            "\n[[bin]]\nname = \"day_{0}\"\npath = \"fuzz_targets/day_{0}.rs\"\n\
+             test = false\ndoc = false\nbench = false\n",
+            day
+        );
+        Some((fuzz_manifest, text + &target))
+    } else {
+        None
+    };
+
+    let fill = |template: &str| {
+        template
+            .replace("{{DAY}}", &day.to_string())
+            .replace("{{TITLE}}", &format!("{:?}", title))
+    };
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(dir.join("examples"))?;
+    fs::write(dir.join("Cargo.toml"), fill(CARGO_TOML))?;
+    fs::write(dir.join("src/lib.rs"), fill(LIB_RS))?;
+    fs::write(dir.join("src/main.rs"), fill(MAIN_RS))?;
+    fs::write(dir.join("input"), "")?;
+    fs::write(dir.join("examples/ex1.txt"), "")?;
+
+    if let Some((manifest, text)) = fuzz_registration {
+        let target = workspace.join(format!("fuzz/fuzz_targets/day_{}.rs", day));
+        fs::write(target, fill(FUZZ_TARGET_RS))?;
+        fs::write(manifest, text)?;
+    }
+
+    for (path, text) in registrations {
+        fs::write(path, text)?;
+    }
+    Ok(())
+}
+
+/// Inserts `line` among the lines mentioning `day_N`, keeping them in day order. Returns `None`
+/// if there are no such lines to insert it next to.
+fn insert_day_line(text: &str, day: u8, line: &str) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let day_lines = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| mentioned_day(l).map(|d| (i, d)))
+        .collect::<Vec<_>>();
+    let position = match day_lines.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => i,
+        None => day_lines.last()?.0 + 1,
+    };
+    lines.insert(position, line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Day of a line starting with `day_N`, possibly quoted or borrowed (`"day_N"`, `&day_N`).
+fn mentioned_day(line: &str) -> Option<u8> {
+    let rest = line
+        .trim_start()
+        .trim_start_matches(['"', '&'])
+        .strip_prefix("day_")?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..digits].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_day_line() {
+        let members = "members = [\n    \"aoc\",\n    \"day_1\",\n    \"day_3\",\n]\n";
+        assert_eq!(
+            insert_day_line(members, 2, "    \"day_2\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day_1\",\n    \"day_2\",\n    \"day_3\",\n]\n"
+        );
+        let registry = "&[\n    &day_9::Day9,\n    &day_10::Day10,\n];\n";
+        assert_eq!(
+            insert_day_line(registry, 11, "    &day_11::Day11,").unwrap(),
+            "&[\n    &day_9::Day9,\n    &day_10::Day10,\n    &day_11::Day11,\n];\n"
+        );
+        assert_eq!(insert_day_line("[dependencies]\n", 1, "day_1 = 1"), None);
+    }
+
+    #[test]
+    fn test_new_day() {
+        let workspace = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(workspace.join("aoc/src")).unwrap();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "members = [\n    \"day_1\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("aoc/Cargo.toml"),
+            "day_1 = { path = \"../day_1\"}\n",
+        )
+        .unwrap();
+        fs::write(workspace.join("aoc/src/registry.rs"), "    &day_1::Day1,\n").unwrap();
+        fs::create_dir_all(workspace.join("fuzz/fuzz_targets")).unwrap();
+        fs::write(
+            workspace.join("fuzz/Cargo.toml"),
+            "[dependencies]\nday_1 = { path = \"../day_1\"}\n",
+        )
+        .unwrap();
+
+        new_day(&workspace, 2, "Gift Shop").unwrap();
+        let lib = fs::read_to_string(workspace.join("day_2/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day2;") && lib.contains("const DAY: u8 = 2;"));
+        assert!(lib.contains("const TITLE: &'static str = \"Gift Shop\";"));
+        assert_eq!(
+            fs::read_to_string(workspace.join("day_2/input")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(workspace.join("aoc/src/registry.rs")).unwrap(),
+            "    &day_1::Day1,\n    &day_2::Day2,\n"
+        );
+        let fuzz_manifest = fs::read_to_string(workspace.join("fuzz/Cargo.toml")).unwrap();
+        assert!(
+            fuzz_manifest.contains("day_2 = { path = \"../day_2\"}\n\n[[bin]]\nname = \"day_2\"")
+        );
+        let target = fs::read_to_string(workspace.join("fuzz/fuzz_targets/day_2.rs")).unwrap();
+        assert!(target.contains("parse_and_solve::<day_2::Day2>"));
+        assert!(new_day(&workspace, 2, "Gift Shop").is_err());
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{registry, run};
+use common::{Measurement, Part, Status, measure};
+use serde_json::json;
+use std::error::Error;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Inputs larger than this are rejected, the real ones are a few dozen KiB
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+/// How long a client has to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves `POST /{year}/day/{n}/part/{p}` (or `/day/{n}/part/{p}` for the latest year) with the
+/// input as body, answering with the measurement of the part as JSON (like `--format json`). Each
+/// connection is handled on its own thread, and closed after its response.
+pub fn serve(listener: TcpListener, timeout: Option<Duration>) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, timeout) {
+                eprintln!("Connection failed: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, timeout: Option<Duration>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let (status, body) = match read_request(&mut stream) {
+        Ok((method, path, body)) => respond(&method, &path, body, timeout),
+        Err(e) => (400, error_body(&e.to_string())),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// The method, path and body of an HTTP/1.1 request.
+fn read_request(stream: &mut TcpStream) -> Result<(String, String, String), Box<dyn Error>> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut words = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Err(format!("Invalid request line {:?}", request_line.trim_end()).into());
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Err("Unexpected end of the headers".into());
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid Content-Length {:?}", value.trim()))?;
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(format!("The body is larger than {} bytes", MAX_BODY_BYTES).into());
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).map_err(|_| "The body is not valid UTF-8")?;
+    Ok((method.to_string(), path.to_string(), body))
+}
+
+/// The status code and JSON body of the response.
+fn respond(method: &str, path: &str, input: String, timeout: Option<Duration>) -> (u16, String) {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (year, day, part) = match segments[..] {
+        [year, "day", day, "part", part] => (year.parse().ok().and_then(registry::year), day, part),
+        ["day", day, "part", part] => (Some(registry::latest()), day, part),
+        _ => return (404, error_body("Expected a path like /2025/day/7/part/2")),
+    };
+    if method != "POST" {
+        return (
+            405,
+            error_body("Expected a POST request, with the input as body"),
+        );
+    }
+    let Some(year) = year else {
+        return (
+            404,
+            error_body(&format!("No solutions for {}", segments[0])),
+        );
+    };
+    let Some(solution) = day.parse().ok().and_then(|day| year.find(day)) else {
+        return (
+            404,
+            error_body(&format!("No solution for day {} of {}", day, year.year)),
+        );
+    };
+    let part = match part.parse::<Part>() {
+        Ok(part) => part,
+        Err(e) => return (404, error_body(&e)),
+    };
+
+    let day = solution.day();
+    let input = match run::catch_panic(|| solution.parse_input(&input)) {
+        Ok(input) => Arc::new(input),
+        Err(e) => return (400, error_body(&format!("Invalid input: {}", e))),
+    };
+    let m = run::with_timeout(day, part, timeout, move || {
+        measure(day, part, || {
+            run::catch_panic(|| solution.run_part(&input, part))
+        })
+    });
+    let status = match m.status {
+        Status::Ok | Status::Wrong => 200,
+        Status::Failed => 500,
+        Status::TimedOut => 504,
+        Status::Skipped => 400,
+    };
+    (status, measurement_body(&m))
+}
+
+fn measurement_body(m: &Measurement) -> String {
+    serde_json::to_string(m).expect("Measurements can always be serialized")
+}
+
+fn error_body(message: &str) -> String {
+    json!({ "error": message }).to_string()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Sends a request to a server on a loopback port, and returns the status code and JSON body
+    /// of the response.
+    fn request(method: &str, path: &str, body: &str) -> (u16, Value) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, None));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_serve() {
+        let example = include_str!("../../day_1/examples/ex1.txt");
+        let (status, body) = request("POST", "/2025/day/1/part/2", example);
+        assert_eq!(status, 200);
+        assert_eq!(body["result"], "6");
+        assert_eq!(body["part"], 2);
+        assert!(body["duration_ns"].is_u64());
+
+        let (status, body) = request("POST", "/day/1/part/1", "L68\nX1\n");
+        assert_eq!(status, 400);
+        assert!(body["error"].as_str().unwrap().contains("2:1: expected"));
+
+        assert_eq!(request("GET", "/day/1/part/1", "").0, 405);
+        assert_eq!(request("POST", "/day/99/part/1", "").0, 404);
+        assert_eq!(request("POST", "/1999/day/1/part/1", "").0, 404);
+        assert_eq!(request("POST", "/days", "").0, 404);
+    }
+}
@@ -0,0 +1,11 @@
+[package]
+name = "day_{{DAY}}"
+version = "0.1.0"
+authors = ["Guillaume Galy <guilgaly@gmail.com>"]
+edition = "2024"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+
+common = { path = "../common"}
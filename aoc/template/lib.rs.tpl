@@ -0,0 +1,79 @@
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Line, ParseError, Solution, lines, parse_each_line};
+use std::error::Error;
+
+pub struct Day{{DAY}};
+
+impl Solution for Day{{DAY}} {
+    const DAY: u8 = {{DAY}};
+    const TITLE: &'static str = {{TITLE}};
+    const INPUT_SIZE: usize = 100;
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        parse_input(input)
+    }
+
+    fn validate(input: &str) -> Vec<ParseError> {
+        parse_each_line(input, parse_line).1
+    }
+
+    fn part_1(lines: &Vec<String>) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(lines))
+    }
+
+    fn part_2(lines: &Vec<String>) -> Result<usize, Box<dyn Error>> {
+        Ok(part_2(lines))
+    }
+
+    /// `size` lines.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+fn part_1(lines: &[String]) -> usize {
+    lines.len()
+}
+
+fn part_2(lines: &[String]) -> usize {
+    lines.len()
+}
+
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    lines(input).map(|line| parse_line(&line)).collect()
+}
+
+fn parse_line(line: &Line) -> Result<String, ParseError> {
+    if line.text.is_empty() {
+        Err(line.error("a non-empty line"))
+    } else {
+        Ok(line.text.to_owned())
+    }
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n", rng.random_range(1..1000)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Paste the puzzle's example in `examples/ex1.txt`, and its expected answers here
+    common::example_tests! {
+        Day{{DAY}},
+        ex1: { part_1: 0, part_2: 0 },
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day{{DAY}}>(20);
+    }
+}
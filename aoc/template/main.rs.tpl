@@ -0,0 +1,6 @@
+use day_{{DAY}}::Day{{DAY}};
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    common::run::<Day{{DAY}}>()
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+/// Global allocator counting the allocations, once enabled with [`enable_alloc_counting`]. To
+/// install it in a binary:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: common::CountingAllocator = common::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+// Signed, since memory allocated before counting was enabled can be freed afterwards
+static LIVE_BYTES: AtomicI64 = AtomicI64::new(0);
+static PEAK_BYTES: AtomicI64 = AtomicI64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    /// Counted as a new allocation of `new_size` bytes, replacing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+fn record_dealloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        LIVE_BYTES.fetch_sub(size as i64, Ordering::Relaxed);
+    }
+}
+
+/// Starts counting the allocations. Fails if the [`CountingAllocator`] is not the global
+/// allocator, since nothing would be counted.
+pub fn enable_alloc_counting() -> Result<(), Box<dyn Error>> {
+    ENABLED.store(true, Ordering::Relaxed);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(std::hint::black_box(Box::new(0u64)));
+    if ALLOCATIONS.load(Ordering::Relaxed) == before {
+        ENABLED.store(false, Ordering::Relaxed);
+        return Err("The counting allocator is not installed as the global allocator".into());
+    }
+    Ok(())
+}
+
+/// Allocations made while running some code.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Total size of the allocations, even if they were freed since
+    pub allocated_bytes: u64,
+    /// Maximum size of the memory allocated at the same time, on top of what was already
+    /// allocated before
+    pub peak_bytes: u64,
+}
+
+/// Runs `f`, counting its allocations if counting is enabled. The allocations of all the threads
+/// are counted, so `f` should not run concurrently with other code.
+pub fn track_allocs<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(live_bytes, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+        peak_bytes: (PEAK_BYTES.load(Ordering::Relaxed) - live_bytes).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.50 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_without_allocator() {
+        // Tests run with the system allocator
+        assert!(enable_alloc_counting().is_err());
+        assert_eq!(track_allocs(|| vec![1u8; 10].len()), (10, None));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Part;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Known answers for a day's input, as stored in its `answers.toml` (next to the `input` file).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    pub const FILE_NAME: &'static str = "answers.toml";
+
+    /// A missing file simply means that no answers are known yet.
+    pub fn load(path: &Path) -> Result<Answers, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| format!("Invalid answers file {}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("Cannot read answers file {}: {}", path.display(), e).into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, toml::to_string(self)?)
+            .map_err(|e| format!("Cannot write answers file {}: {}", path.display(), e).into())
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_1.as_deref(),
+            Part::Two => self.part_2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: String) {
+        match part {
+            Part::One => self.part_1 = Some(answer),
+            Part::Two => self.part_2 = Some(answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mut answers = Answers::default();
+        answers.set(Part::Two, "305999729392659".to_string());
+        let toml = toml::to_string(&answers).unwrap();
+        assert_eq!(toml, "part_2 = \"305999729392659\"\n");
+        let parsed: Answers = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.get(Part::One), None);
+        assert_eq!(parsed.get(Part::Two), Some("305999729392659"));
+    }
+}
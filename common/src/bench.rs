@@ -0,0 +1,178 @@
+use crate::Part;
+use crate::alloc_stats::{AllocStats, track_allocs};
+use crate::measurement::{Measurement, serialize_nanos};
+use serde::Serialize;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// How long to benchmark a part for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Budget {
+    /// Exactly this many timed runs
+    Runs(u32),
+    /// As many timed runs as fit in this duration (but always at least one)
+    Time(Duration),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BenchConfig {
+    /// Untimed runs before the measured ones, to warm up caches and the branch predictor
+    pub warmup: u32,
+    pub budget: Budget,
+}
+
+impl Default for BenchConfig {
+    fn default() -> BenchConfig {
+        BenchConfig { warmup: 1, budget: Budget::Runs(10) }
+    }
+}
+
+/// Summary of the timed runs of a benchmark.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct BenchStats {
+    pub samples: usize,
+    #[serde(rename = "min_ns", serialize_with = "serialize_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "serialize_nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "serialize_nanos")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_ns", serialize_with = "serialize_nanos")]
+    pub stddev: Duration,
+    #[serde(rename = "p95_ns", serialize_with = "serialize_nanos")]
+    pub p95: Duration,
+}
+
+impl BenchStats {
+    /// Panics if there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> BenchStats {
+        assert!(
+            !samples.is_empty(),
+            "Cannot compute statistics without samples"
+        );
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean_secs = samples.iter().map(|s| s.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples
+                .iter()
+                .map(|s| (s.as_secs_f64() - mean_secs).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+        // Nearest-rank percentile
+        let p95_rank = (0.95 * n as f64).ceil() as usize;
+        BenchStats {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95: samples[p95_rank.max(1) - 1],
+        }
+    }
+}
+
+/// Runs `f` repeatedly according to `config`, and returns the result of the first run along with
+/// timing statistics. Stops at the first error.
+pub fn bench<T, E>(
+    config: &BenchConfig,
+    f: impl Fn() -> Result<T, E>,
+) -> Result<(T, BenchStats), E> {
+    let mut result = None;
+    for _ in 0..config.warmup {
+        let res = f()?;
+        result.get_or_insert(res);
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    loop {
+        let before = Instant::now();
+        let res = f()?;
+        samples.push(before.elapsed());
+        result.get_or_insert(res);
+
+        let done = match config.budget {
+            Budget::Runs(runs) => samples.len() >= runs as usize,
+            Budget::Time(time) => start.elapsed() >= time,
+        };
+        if done {
+            break;
+        }
+    }
+
+    Ok((
+        result.expect("At least one run"),
+        BenchStats::from_samples(samples),
+    ))
+}
+
+/// Benchmarks `f` as the given part of a day; the measurement's duration is the median run time.
+pub fn measure_bench<T, E>(
+    day: u8,
+    part: Part,
+    config: &BenchConfig,
+    f: impl Fn() -> Result<T, E>,
+) -> Measurement
+where
+    T: Display,
+    E: Display,
+{
+    let before = Instant::now();
+    match track_allocs(|| bench(config, f)) {
+        (Ok((result, stats)), alloc) => {
+            // Every run is expected to allocate the same
+            let runs = (config.warmup as u64 + stats.samples as u64).max(1);
+            Measurement {
+                stats: Some(stats),
+                alloc: alloc.map(|a| AllocStats {
+                    allocations: a.allocations / runs,
+                    allocated_bytes: a.allocated_bytes / runs,
+                    peak_bytes: a.peak_bytes,
+                }),
+                ..Measurement::ok(day, part, result.to_string(), stats.median)
+            }
+        }
+        (Err(e), _) => Measurement::failed(day, part, e.to_string(), before.elapsed()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_stats() {
+        let samples = (1..=20).map(Duration::from_millis).collect();
+        let stats = BenchStats::from_samples(samples);
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.mean, Duration::from_micros(10500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.stddev.as_micros(), 5916);
+    }
+
+    #[test]
+    fn test_bench_runs() {
+        let calls = Cell::new(0);
+        let config = BenchConfig { warmup: 2, budget: Budget::Runs(5) };
+        let (result, stats) = bench(&config, || {
+            calls.set(calls.get() + 1);
+            Ok::<_, String>(calls.get())
+        })
+        .unwrap();
+        assert_eq!(result, 1);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(calls.get(), 7);
+    }
+}
@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Shared flag asking a running part to stop, e.g. when it ran out of time.
+///
+/// Solutions don't receive the token: the runner installs it for the thread running the part,
+/// and long loops poll it with [`check_cancelled`].
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// A token which also cancels itself at `deadline`, without a thread to do it.
+    pub fn with_deadline(deadline: Instant) -> CancelToken {
+        CancelToken { deadline: Some(deadline), ..CancelToken::default() }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Runs `f` with this token as the current thread's token, until it returns or panics.
+    pub fn install<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let _restore = Restore(previous);
+        f()
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Puts back the previous token when dropped, so that a panicking part doesn't leave its token
+/// installed on a thread which runs other parts afterwards.
+struct Restore(Option<CancelToken>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.replace(self.0.take()));
+    }
+}
+
+/// Fails if the current thread's token was cancelled; never fails without a token.
+pub fn check_cancelled() -> Result<(), Cancelled> {
+    let cancelled = CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    });
+    if cancelled { Err(Cancelled) } else { Ok(()) }
+}
+
+/// Error of a part which stopped because it was cancelled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_cancelled() {
+        assert_eq!(check_cancelled(), Ok(()));
+        let token = CancelToken::new();
+        token.install(|| {
+            assert_eq!(check_cancelled(), Ok(()));
+            token.cancel();
+            assert_eq!(check_cancelled(), Err(Cancelled));
+        });
+        assert_eq!(check_cancelled(), Ok(()));
+    }
+
+    #[test]
+    fn test_restored_after_panic() {
+        let token = CancelToken::new();
+        token.cancel();
+        let result = std::panic::catch_unwind(|| token.install(|| panic!("solver bug")));
+        assert!(result.is_err());
+        assert_eq!(check_cancelled(), Ok(()));
+    }
+
+    #[test]
+    fn test_deadline() {
+        assert!(CancelToken::with_deadline(Instant::now()).is_cancelled());
+        let later = Instant::now() + std::time::Duration::from_secs(60);
+        assert!(!CancelToken::with_deadline(later).is_cancelled());
+    }
+}
@@ -0,0 +1,66 @@
+/// Generates a test module per example fixture `examples/<name>.txt` of the calling crate, with
+/// one test per part validating the example, running the real parser and checking the expected answer
+/// (of the alternative implementations too):
+///
+/// ```ignore
+/// common::example_tests! {
+///     Day11,
+///     ex1: { part_1: 5 },
+///     ex2: { part_2: 2 },
+/// }
+/// ```
+///
+/// The solution type is looked up in the module where the macro is invoked.
+#[macro_export]
+macro_rules! example_tests {
+    (@alternatives $solution:ty, part_1) => {
+        <$solution as $crate::Solution>::ALTERNATIVES_1
+    };
+    (@alternatives $solution:ty, part_2) => {
+        <$solution as $crate::Solution>::ALTERNATIVES_2
+    };
+    ($solution:ty, $($example:ident: { $($part:ident: $expected:expr),+ $(,)? }),+ $(,)?) => {
+        $(
+            mod $example {
+                #[allow(unused_imports)]
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $part() {
+                        let input = $crate::example!($example);
+                        $crate::ValidationError::check(<$solution as $crate::Solution>::validate(input))
+                            .unwrap_or_else(|e| panic!("Invalid example:\n{}", e));
+                        let input = <$solution as $crate::Solution>::parse(input)
+                            .unwrap_or_else(|e| panic!("Invalid example:\n{}", e));
+                        assert_eq!(
+                            <$solution as $crate::Solution>::$part(&input).unwrap(),
+                            $expected
+                        );
+                        for alternative in $crate::example_tests!(@alternatives $solution, $part) {
+                            assert_eq!(
+                                (alternative.run)(&input).unwrap(),
+                                $expected,
+                                "Alternative {:?}",
+                                alternative.name
+                            );
+                        }
+                    }
+                )+
+            }
+        )+
+    };
+}
+
+/// Contents of the example fixture `examples/<name>.txt` of the calling crate.
+#[macro_export]
+macro_rules! example {
+    ($example:ident) => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/",
+            stringify!($example),
+            ".txt"
+        ))
+    };
+}
@@ -0,0 +1,25 @@
+use crate::{CancelToken, Solution};
+use std::str;
+use std::time::{Duration, Instant};
+
+/// Time after which the parts are cancelled, like with the runner's `--timeout`, so that
+/// brute-forcing a huge input is an error rather than a hang.
+const PART_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Validates, parses and solves arbitrary bytes, like the runner would. Any input must give an
+/// answer or an error: fuzzing looks for the ones which panic (overflows included) or hang.
+pub fn parse_and_solve<S: Solution>(data: &[u8]) {
+    let Ok(input) = str::from_utf8(data) else {
+        return;
+    };
+    let errors = S::validate(input);
+    let Ok(input) = S::parse(input) else {
+        return;
+    };
+    // Like the runner, which doesn't solve the inputs with errors
+    if !errors.is_empty() {
+        return;
+    }
+    let _ = CancelToken::with_deadline(Instant::now() + PART_TIMEOUT).install(|| S::part_1(&input));
+    let _ = CancelToken::with_deadline(Instant::now() + PART_TIMEOUT).install(|| S::part_2(&input));
+}
@@ -0,0 +1,29 @@
+use crate::Solution;
+use crate::validation::ValidationError;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Generates an input for the day from `seed`: the same seed and size always give the same input.
+pub fn generate<S: Solution>(seed: u64, size: usize) -> String {
+    S::generate(&mut StdRng::seed_from_u64(seed), size)
+}
+
+/// Panics unless the day's generator gives the same input for the same seed, and valid inputs
+/// for a few seeds.
+pub fn check_generator<S: Solution>(size: usize) {
+    for seed in 0..5 {
+        let input = generate::<S>(seed, size);
+        assert_eq!(
+            input,
+            generate::<S>(seed, size),
+            "Different inputs for seed {}",
+            seed
+        );
+        if let Err(e) = ValidationError::check(S::validate(&input)) {
+            panic!("Invalid input for seed {}:\n{}\n\n{}", seed, e, input);
+        }
+        if let Err(e) = S::parse(&input) {
+            panic!("Unparsable input for seed {}:\n{}\n\n{}", seed, e, input);
+        }
+    }
+}
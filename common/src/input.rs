@@ -0,0 +1,97 @@
+use crate::Answers;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the directory where puzzle inputs are looked up.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read a day's puzzle input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// A directory laid out like this workspace, i.e. with the input for day N at `day_N/input`
+    Dir(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a path to the input file.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The directory from [INPUT_DIR_VAR] if set, or else the root of this workspace.
+    pub fn default_dir() -> InputSource {
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(dir) => InputSource::Dir(PathBuf::from(dir)),
+            None => InputSource::Dir(
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .parent()
+                    .expect("The common crate is in the workspace root")
+                    .to_path_buf(),
+            ),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Dir(dir) => Some(dir.join(format!("day_{}", day)).join("input")),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Where the known answers for this input are stored, if anywhere.
+    pub fn answers_path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Dir(dir) => {
+                Some(dir.join(format!("day_{}", day)).join(Answers::FILE_NAME))
+            }
+            InputSource::File(_) | InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, Box<dyn Error>> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| format!("Cannot read input {}: {}", path.display(), e).into()),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("in.txt"),
+            InputSource::File(PathBuf::from("in.txt"))
+        );
+    }
+
+    #[test]
+    fn test_dir_path() {
+        let source = InputSource::Dir(PathBuf::from("inputs"));
+        assert_eq!(source.path(7), Some(PathBuf::from("inputs/day_7/input")));
+        assert_eq!(
+            source.answers_path(7),
+            Some(PathBuf::from("inputs/day_7/answers.toml"))
+        );
+    }
+}
@@ -1,49 +1,51 @@
 pub use ascii;
 pub use itertools;
 pub use lazy_static;
-use std::fmt::Display;
 // pub use lcmx;
 pub use maplit;
 pub use ndarray;
+pub use proptest;
+pub use rand;
 pub use regex;
 pub use sscanf;
 pub use sscanf::regex::Regex;
+pub use tracing;
 
-use std::time::Instant;
+mod alloc_stats;
+mod answers;
+mod bench;
+mod cancel;
+mod example_tests;
+mod fuzzing;
+mod generator;
+mod input;
+mod logging;
+mod measurement;
+mod params;
+mod parse_error;
+pub mod progress;
+mod property;
+mod solution;
+mod validation;
 
-pub fn time_execution_res<T, E>(name: &str, f: impl Fn() -> Result<T, E>) -> Result<T, E>
-where
-    T: Display,
-{
-    let before = Instant::now();
-    match f() {
-        Ok(result) => {
-            println!(
-                "{} elapsed time: {:.2?}, result: {}",
-                name,
-                before.elapsed(),
-                result
-            );
-            Ok(result)
-        }
-        Err(e) => Err(e),
-    }
-}
-
-pub fn time_execution<T>(name: &str, f: impl Fn() -> T) -> T
-where
-    T: Display,
-{
-    let before = Instant::now();
-    let result = f();
-    println!(
-        "{} elapsed time: {:.2?}, result: {}",
-        name,
-        before.elapsed(),
-        result
-    );
-    result
-}
+pub use alloc_stats::{
+    AllocStats, CountingAllocator, enable_alloc_counting, format_bytes, track_allocs,
+};
+pub use answers::Answers;
+pub use bench::{BenchConfig, BenchStats, Budget, bench, measure_bench};
+pub use cancel::{CancelToken, Cancelled, check_cancelled};
+pub use fuzzing::parse_and_solve;
+pub use generator::{check_generator, generate};
+pub use input::{INPUT_DIR_VAR, InputSource};
+pub use logging::{LOG_VAR, init_logging};
+pub use measurement::{Measurement, OutputFormat, Status, measure};
+pub use params::Param;
+pub use parse_error::{Line, ParseError, lines};
+pub use property::check_against_reference;
+pub use solution::{
+    Alternative, AnySolution, MAIN_IMPLEMENTATION, ParsedInput, Part, Query, Solution, run,
+};
+pub use validation::{ValidationError, parse_each_line};
 
 #[cfg(test)]
 mod tests {
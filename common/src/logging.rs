@@ -0,0 +1,31 @@
+//! Diagnostic output, with the [tracing](https://docs.rs/tracing) crate: the parsing and each
+//! part run in a span (`parse`, `part_1`, `part_2`), and solvers can log their own events, e.g.
+//!
+//! ```ignore
+//! tracing::debug!(round, removed, "removed the accessible rolls");
+//! ```
+//!
+//! Nothing is collected unless [LOG_VAR] is set, e.g. `AOC_LOG=debug` or `AOC_LOG=day_8=debug`
+//! (the syntax of `RUST_LOG`), so the events cost next to nothing by default.
+
+use std::env;
+use std::io;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Environment variable enabling the diagnostic output, with a filter like `debug`.
+pub const LOG_VAR: &str = "AOC_LOG";
+
+/// Prints the events and the closed spans (with their duration) enabled by [LOG_VAR] to stderr.
+/// Does nothing if the variable is not set, or if a subscriber is already installed.
+pub fn init_logging() {
+    let Some(filter) = env::var_os(LOG_VAR) else {
+        return;
+    };
+    let filter = EnvFilter::builder().parse_lossy(filter.to_string_lossy());
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(io::stderr)
+        .try_init();
+}
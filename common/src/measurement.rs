@@ -0,0 +1,311 @@
+use crate::Part;
+use crate::alloc_stats::{AllocStats, format_bytes, track_allocs};
+use crate::bench::BenchStats;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Result and timing of running one part of a day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: Part,
+    pub result: Option<String>,
+    /// Only set when the result was checked against a known answer
+    pub expected: Option<String>,
+    #[serde(rename = "duration_ns", serialize_with = "serialize_nanos")]
+    pub duration: Duration,
+    pub status: Status,
+    pub error: Option<String>,
+    /// Only set when the part was benchmarked
+    pub stats: Option<BenchStats>,
+    /// Only set when allocation counting is enabled; per run when benchmarking
+    pub alloc: Option<AllocStats>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Failed,
+    /// Solved, but the result differs from the known answer
+    Wrong,
+    /// Stopped for running longer than the timeout
+    TimedOut,
+    /// Not run, because the day's input couldn't be read or parsed
+    Skipped,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Failed => "failed",
+            Status::Wrong => "wrong",
+            Status::TimedOut => "timed_out",
+            Status::Skipped => "skipped",
+        }
+    }
+}
+
+impl Measurement {
+    pub fn ok(day: u8, part: Part, result: String, duration: Duration) -> Measurement {
+        Measurement {
+            day,
+            part,
+            result: Some(result),
+            expected: None,
+            duration,
+            status: Status::Ok,
+            error: None,
+            stats: None,
+            alloc: None,
+        }
+    }
+
+    pub fn failed(day: u8, part: Part, error: String, duration: Duration) -> Measurement {
+        Measurement {
+            day,
+            part,
+            result: None,
+            expected: None,
+            duration,
+            status: Status::Failed,
+            error: Some(error),
+            stats: None,
+            alloc: None,
+        }
+    }
+
+    pub fn timed_out(day: u8, part: Part, duration: Duration) -> Measurement {
+        Measurement {
+            status: Status::TimedOut,
+            ..Measurement::failed(day, part, "timed out".to_string(), duration)
+        }
+    }
+
+    pub fn skipped(day: u8, part: Part) -> Measurement {
+        Measurement {
+            status: Status::Skipped,
+            error: None,
+            ..Measurement::failed(day, part, String::new(), Duration::ZERO)
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == Status::Ok
+    }
+
+    /// Compares the result with the known answer, marking the measurement as wrong if they differ.
+    pub fn check(&mut self, expected: &str) {
+        if self.status == Status::Ok && self.result.as_deref() != Some(expected) {
+            self.status = Status::Wrong;
+        }
+        self.expected = Some(expected.to_string());
+    }
+}
+
+/// Runs and times `f` as the given part of a day.
+pub fn measure<T, E>(day: u8, part: Part, f: impl Fn() -> Result<T, E>) -> Measurement
+where
+    T: Display,
+    E: Display,
+{
+    let before = Instant::now();
+    let (result, alloc) = track_allocs(f);
+    let duration = before.elapsed();
+    let m = match result {
+        Ok(result) => Measurement::ok(day, part, result.to_string(), duration),
+        Err(e) => Measurement::failed(day, part, e.to_string(), duration),
+    };
+    Measurement { alloc, ..m }
+}
+
+pub(crate) fn serialize_nanos<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+/// How measurements are printed: as human-readable lines, JSON lines, or CSV records.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    /// Line to print once before the measurements, if any.
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            OutputFormat::Csv => Some(
+                "day,part,result,expected,duration_ns,status,error,\
+                 samples,min_ns,median_ns,mean_ns,stddev_ns,p95_ns,\
+                 allocations,allocated_bytes,peak_bytes",
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn format(&self, m: &Measurement) -> String {
+        match self {
+            OutputFormat::Human => format_human(m),
+            OutputFormat::Json => {
+                serde_json::to_string(m).expect("Measurement is always serializable")
+            }
+            OutputFormat::Csv => [
+                m.day.to_string(),
+                u8::from(m.part).to_string(),
+                csv_field(m.result.as_deref().unwrap_or("")),
+                csv_field(m.expected.as_deref().unwrap_or("")),
+                m.duration.as_nanos().to_string(),
+                m.status.as_str().to_string(),
+                csv_field(m.error.as_deref().unwrap_or("")),
+            ]
+            .into_iter()
+            .chain(match &m.stats {
+                Some(s) => [
+                    s.samples.to_string(),
+                    s.min.as_nanos().to_string(),
+                    s.median.as_nanos().to_string(),
+                    s.mean.as_nanos().to_string(),
+                    s.stddev.as_nanos().to_string(),
+                    s.p95.as_nanos().to_string(),
+                ],
+                None => Default::default(),
+            })
+            .chain(match &m.alloc {
+                Some(a) => [
+                    a.allocations.to_string(),
+                    a.allocated_bytes.to_string(),
+                    a.peak_bytes.to_string(),
+                ],
+                None => Default::default(),
+            })
+            .join(","),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<OutputFormat, String> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "{:?} is not an output format, expected human, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Human => write!(f, "human"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+fn format_human(m: &Measurement) -> String {
+    let Some(result) = &m.result else {
+        match m.status {
+            Status::TimedOut => return format!("{} timed out after {:.2?}", m.part, m.duration),
+            Status::Skipped => return format!("{} skipped", m.part),
+            _ => {}
+        }
+        return format!(
+            "{} failed after {:.2?}: {}",
+            m.part,
+            m.duration,
+            m.error.as_deref().unwrap_or("unknown error")
+        );
+    };
+    let time = match &m.stats {
+        Some(s) => format!(
+            "{} median time: {:.2?} (min {:.2?}, mean {:.2?}, stddev {:.2?}, p95 {:.2?}, \
+             {} runs)",
+            m.part, s.median, s.min, s.mean, s.stddev, s.p95, s.samples
+        ),
+        None => format!("{} elapsed time: {:.2?}", m.part, m.duration),
+    };
+    let alloc = match &m.alloc {
+        Some(a) => format!(
+            ", {} allocations ({}), peak memory: {}",
+            a.allocations,
+            format_bytes(a.allocated_bytes),
+            format_bytes(a.peak_bytes)
+        ),
+        None => String::new(),
+    };
+    let line = format!("{}{}, result: {}", time, alloc, result);
+    match (&m.status, &m.expected) {
+        (Status::Wrong, Some(expected)) => format!(
+            "{}\n  WRONG ANSWER\n  - expected: {}\n  + actual:   {}",
+            line, expected, result
+        ),
+        _ => line,
+    }
+}
+
+/// Quotes a CSV field if needed (RFC 4180).
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut m = Measurement::ok(7, Part::One, "21".to_string(), Duration::ZERO);
+        m.check("21");
+        assert_eq!(m.status, Status::Ok);
+        m.check("22");
+        assert_eq!(m.status, Status::Wrong);
+        assert_eq!(
+            OutputFormat::Human.format(&m),
+            "Part 1 elapsed time: 0.00ns, result: 21\n  WRONG ANSWER\n  - expected: 22\n  + actual:   21"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let m = Measurement::ok(7, Part::Two, "40".to_string(), Duration::from_micros(3));
+        assert_eq!(
+            OutputFormat::Json.format(&m),
+            r#"{"day":7,"part":2,"result":"40","expected":null,"duration_ns":3000,"status":"ok","error":null,"stats":null,"alloc":null}"#
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let m = Measurement::failed(
+            6,
+            Part::One,
+            "\"x\", y".to_string(),
+            Duration::from_nanos(5),
+        );
+        assert_eq!(
+            OutputFormat::Csv.format(&m),
+            r#"6,1,,,5,failed,"""x"", y",,,,,,,,,"#
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Named parameters of the days, e.g. the number of connections of day 8, which the runner can
+//! override (from `aoc.toml` or the command line) without recompiling.
+//!
+//! Like cancellation and progress, solutions don't receive them: they read them with
+//! [Solution::param](crate::Solution::param), which falls back to the declared default.
+
+use crate::Solution;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+
+/// A parameter of a day, declared in [Solution::PARAMS](crate::Solution::PARAMS).
+pub struct Param {
+    pub name: &'static str,
+    /// The value used unless overridden, e.g. `1000`
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+/// The overridden values of each day's parameters, by solution type (the days of different years
+/// share their numbers).
+static OVERRIDES: LazyLock<RwLock<HashMap<TypeId, HashMap<String, String>>>> =
+    LazyLock::new(Default::default);
+
+/// Replaces the overridden values of the parameters of `S`, which must all be declared.
+pub(crate) fn set<S: Solution + 'static>(
+    values: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    if let Some((name, _)) = values
+        .iter()
+        .find(|(name, _)| !S::PARAMS.iter().any(|p| p.name == name))
+    {
+        return Err(format!(
+            "Day {} has no parameter `{}`, expected one of: {}",
+            S::DAY,
+            name,
+            S::PARAMS
+                .iter()
+                .map(|p| p.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .into());
+    }
+    OVERRIDES
+        .write()
+        .expect("The overrides are never left half-written")
+        .insert(TypeId::of::<S>(), values.iter().cloned().collect());
+    Ok(())
+}
+
+/// The value of the parameter `name` of `S`: overridden, or else the default.
+pub(crate) fn get<S, T>(name: &str) -> Result<T, Box<dyn Error>>
+where
+    S: Solution + 'static,
+    T: FromStr,
+    T::Err: Display,
+{
+    let param = S::PARAMS
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Day {} has no parameter `{}`", S::DAY, name))?;
+    let overrides = OVERRIDES
+        .read()
+        .expect("The overrides are never left half-written");
+    let value = overrides
+        .get(&TypeId::of::<S>())
+        .and_then(|values| values.get(name))
+        .map_or(param.default, String::as_str);
+    value
+        .parse()
+        .map_err(|e| format!("Invalid value `{}` for parameter `{}`: {}", value, name, e).into())
+}
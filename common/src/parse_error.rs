@@ -0,0 +1,195 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Error in a puzzle input, pointing at the offending text like a compiler diagnostic:
+///
+/// ```text
+/// 3:2: expected a number of clicks, found `x5`
+///   |
+/// 3 | Rx5
+///   |  ^^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    /// The offending text, empty if something is missing
+    pub snippet: String,
+    /// What was expected instead, e.g. "a number of clicks"
+    pub expected: String,
+    /// The whole offending line
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Error at the given byte offset of a line, covering `len` bytes.
+    pub fn new(line: &Line, offset: usize, len: usize, expected: impl Into<String>) -> ParseError {
+        let offset = floor_char_boundary(line.text, offset.min(line.text.len()));
+        let end = floor_char_boundary(line.text, (offset + len).min(line.text.len()));
+        ParseError {
+            line: line.number,
+            column: line.text[..offset].chars().count() + 1,
+            snippet: line.text[offset..end].to_string(),
+            expected: expected.into(),
+            source_line: line.text.to_string(),
+        }
+    }
+
+    /// Error for something missing at the very end of the input.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> ParseError {
+        let last = lines(input).last().unwrap_or(Line { number: 1, text: "" });
+        ParseError::new(&last, last.text.len(), 0, expected)
+    }
+
+    /// Error about a value built outside of an input, e.g. by a checked constructor, pointing
+    /// at the whole of `text`, the value as it would be written in an input.
+    pub fn in_value(text: &str, expected: impl Into<String>) -> ParseError {
+        Line { number: 1, text }.error(expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = if self.snippet.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("`{}`", self.snippet)
+        };
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "{}:{}: expected {}, found {}",
+            self.line, self.column, self.expected, found
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.snippet.chars().count().max(1))
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of a puzzle input, with its 1-based line number.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Iterates over the lines of the input, with their line numbers.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line { number: i + 1, text })
+}
+
+impl<'a> Line<'a> {
+    /// Error covering the whole line.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self, 0, self.text.len(), expected)
+    }
+
+    /// Error pointing at `snippet`, which must be a slice of this line's text (as returned by
+    /// `split`, `trim`, etc.); otherwise the error covers the whole line.
+    pub fn error_at(&self, snippet: &str, expected: impl Into<String>) -> ParseError {
+        match (snippet.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize) {
+            Some(offset) if offset + snippet.len() <= self.text.len() => {
+                ParseError::new(self, offset, snippet.len(), expected)
+            }
+            _ => self.error(expected),
+        }
+    }
+
+    /// Error pointing at the character starting at byte `offset` (or at the end of the line).
+    pub fn error_at_char(&self, offset: usize, expected: impl Into<String>) -> ParseError {
+        let len = self.text[offset..]
+            .chars()
+            .next()
+            .map_or(0, |c| c.len_utf8());
+        ParseError::new(self, offset, len, expected)
+    }
+
+    /// Parses `snippet`, a slice of this line's text.
+    pub fn parse_at<T: FromStr>(
+        &self,
+        snippet: &'a str,
+        expected: impl Into<String>,
+    ) -> Result<T, ParseError> {
+        snippet
+            .parse()
+            .map_err(|_| self.error_at(snippet, expected))
+    }
+
+    /// Parses all the `sep`-separated items of `snippet`, a slice of this line's text.
+    pub fn parse_separated<T: FromStr>(
+        &self,
+        snippet: &'a str,
+        sep: char,
+        expected: &str,
+    ) -> Result<Vec<T>, ParseError> {
+        snippet
+            .split(sep)
+            .map(|item| self.parse_at(item, expected))
+            .collect()
+    }
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let line = Line { number: 3, text: "Rx5" };
+        let error = line.parse_at::<u32>(&line.text[1..], "a number of clicks");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "3:2: expected a number of clicks, found `x5`\n  |\n3 | Rx5\n  |  ^^"
+        );
+    }
+
+    #[test]
+    fn test_at_end() {
+        let error = ParseError::at_end("1-2\n3-4\n", "a blank line");
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.snippet, "");
+        let error = ParseError::at_end("", "a grid");
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn test_in_value() {
+        let error = ParseError::in_value("5-3", "a range with start <= end");
+        assert_eq!(
+            error.to_string(),
+            "1:1: expected a range with start <= end, found `5-3`\n  |\n1 | 5-3\n  | ^^^"
+        );
+    }
+
+    #[test]
+    fn test_multi_byte_chars() {
+        let line = Line { number: 1, text: "é@x" };
+        let error = line.error_at_char(3, "`@` or `.`");
+        assert_eq!((error.column, error.snippet.as_str()), (3, "x"));
+        let error = line.error_at("unrelated", "anything");
+        assert_eq!((error.column, error.snippet.as_str()), (1, "é@x"));
+    }
+}
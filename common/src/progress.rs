@@ -0,0 +1,129 @@
+//! Progress of the running part, for solvers which can take a while:
+//!
+//! ```ignore
+//! progress::total(machines.len());
+//! for machine in machines {
+//!     progress::message(format!("{} buttons", machine.buttons.len()));
+//!     ...
+//!     progress::advance(1);
+//! }
+//! ```
+//!
+//! Like cancellation, the runner installs a [`ProgressSink`] for the thread running the part.
+//! Without one (e.g. in tests), the calls do nothing.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Displays the progress of a part.
+pub trait ProgressSink: Send + Sync {
+    /// Called after every change of the progress.
+    fn update(&self, state: &ProgressState);
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ProgressState {
+    /// Number of steps, if known
+    pub total: Option<usize>,
+    pub done: usize,
+    pub message: Option<String>,
+}
+
+struct Tracker {
+    sink: Arc<dyn ProgressSink>,
+    state: ProgressState,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Tracker>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` reporting its progress to `sink`, until it returns or panics.
+pub fn install<T>(sink: Arc<dyn ProgressSink>, f: impl FnOnce() -> T) -> T {
+    let tracker = Tracker { sink, state: ProgressState::default() };
+    let previous = CURRENT.with(|current| current.replace(Some(tracker)));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Puts back the previous tracker when dropped, even if the part panicked (like cancellation).
+struct Restore(Option<Tracker>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.replace(self.0.take()));
+    }
+}
+
+/// Sets the number of steps, and starts over from 0.
+pub fn total(total: usize) {
+    update(|state| {
+        state.total = Some(total);
+        state.done = 0;
+    });
+}
+
+/// Marks `steps` more steps as done.
+pub fn advance(steps: usize) {
+    update(|state| state.done += steps);
+}
+
+/// Describes what is being done, e.g. the current step.
+pub fn message(message: impl Into<String>) {
+    update(|state| state.message = Some(message.into()));
+}
+
+fn update(f: impl FnOnce(&mut ProgressState)) {
+    CURRENT.with(|current| {
+        if let Some(tracker) = current.borrow_mut().as_mut() {
+            f(&mut tracker.state);
+            tracker.sink.update(&tracker.state);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<ProgressState>>);
+
+    impl ProgressSink for Recorder {
+        fn update(&self, state: &ProgressState) {
+            self.0.lock().unwrap().push(state.clone());
+        }
+    }
+
+    #[test]
+    fn test_install() {
+        // Ignored without a sink
+        advance(1);
+
+        let recorder = Arc::new(Recorder::default());
+        install(recorder.clone(), || {
+            total(3);
+            advance(2);
+            message("almost");
+        });
+        advance(1);
+
+        let states = recorder.0.lock().unwrap();
+        assert_eq!(states.len(), 3);
+        assert_eq!(
+            states[2],
+            ProgressState { total: Some(3), done: 2, message: Some("almost".to_string()) }
+        );
+    }
+
+    #[test]
+    fn test_restored_after_panic() {
+        let recorder = Arc::new(Recorder::default());
+        let sink = recorder.clone();
+        let result = std::panic::catch_unwind(move || install(sink, || panic!("solver bug")));
+        assert!(result.is_err());
+        advance(1);
+        assert!(recorder.0.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+//! Property tests comparing an optimized implementation with a straightforward reference, e.g. a
+//! brute force one, on many random small inputs. The inputs come from a
+//! [proptest](https://docs.rs/proptest) strategy, which also shrinks the first disagreeing input
+//! to a minimal one, e.g. in a day's tests:
+//!
+//! ```ignore
+//! common::check_against_reference(
+//!     vec(-300i64..=300, 0..20),
+//!     |rotations| count_zeros_click_by_click(rotations).1,
+//!     |rotations| part_2(rotations),
+//! );
+//! ```
+//!
+//! The runs are deterministic; set `PROPTEST_CASES` to try more inputs than the default 256.
+
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRng, TestRunner};
+use std::fmt::Debug;
+
+/// Panics unless `optimized` gives the same result as `reference` on the inputs generated by
+/// `inputs`, reporting the smallest disagreeing input found and both results.
+pub fn check_against_reference<T, O>(
+    inputs: impl Strategy<Value = T>,
+    reference: impl Fn(&T) -> O,
+    optimized: impl Fn(&T) -> O,
+) where
+    T: Debug,
+    O: PartialEq + Debug,
+{
+    let config = Config {
+        // The failing inputs are reported, rather than saved next to the tests
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let rng = TestRng::deterministic_rng(config.rng_algorithm);
+    let mut runner = TestRunner::new_with_rng(config, rng);
+    let result = runner.run(&inputs, |input| {
+        let (expected, actual) = (reference(&input), optimized(&input));
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(TestCaseError::fail(format!(
+                "expected {:?} (reference), got {:?}",
+                expected, actual
+            )))
+        }
+    });
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, input)) => {
+            panic!("{}, for the minimal input:\n{:#?}", reason, input)
+        }
+        Err(TestError::Abort(reason)) => panic!("Aborted: {}", reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+
+    #[test]
+    fn test_check_against_reference() {
+        check_against_reference(
+            vec(0u32..100, 0..10),
+            |v| v.iter().sum::<u32>(),
+            |v| v.iter().rev().sum(),
+        );
+    }
+
+    #[test]
+    fn test_shrinking() {
+        let panic = std::panic::catch_unwind(|| {
+            check_against_reference(
+                vec(0u32..100, 0..10),
+                |v| v.iter().sum::<u32>(),
+                |v| v.iter().filter(|x| **x < 50).sum(),
+            )
+        })
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(
+            message.starts_with("expected 50 (reference), got 0"),
+            "{}",
+            message
+        );
+        assert!(message.ends_with("[\n    50,\n]"), "{}", message);
+    }
+}
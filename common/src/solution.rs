@@ -0,0 +1,448 @@
+use crate::ParseError;
+use crate::generator;
+use crate::input::InputSource;
+use crate::measurement::{OutputFormat, measure};
+use crate::params;
+use crate::params::Param;
+use crate::validation::ValidationError;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use serde::{Serialize, Serializer};
+use std::any::Any;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A day's puzzle: how to parse its input, and how to solve both parts from the parsed input.
+pub trait Solution {
+    const DAY: u8;
+    /// The puzzle's title, e.g. "Secret Entrance"
+    const TITLE: &'static str;
+    /// Size of the real inputs, in the unit of the `size` of [Solution::generate]
+    const INPUT_SIZE: usize;
+
+    type Input: Send + Sync + 'static;
+    type Output1: Display + 'static;
+    type Output2: Display + 'static;
+
+    /// Other implementations of part 1, which must give the same answers as `part_1`
+    const ALTERNATIVES_1: &'static [Alternative<Self::Input, Self::Output1>] = &[];
+    /// Other implementations of part 2, which must give the same answers as `part_2`
+    const ALTERNATIVES_2: &'static [Alternative<Self::Input, Self::Output2>] = &[];
+    /// Day-specific REPL commands about the parsed input, answered by `query`
+    const QUERIES: &'static [Query] = &[];
+    /// Named parameters of the parts, read with [Solution::param]
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    /// Checks the invariants which the parts rely on, reporting all the problems of the input
+    /// at once instead of only the first one like `parse`. Runs before `parse`.
+    fn validate(_input: &str) -> Vec<ParseError> {
+        Vec::new()
+    }
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, Box<dyn Error>>;
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, Box<dyn Error>>;
+    /// Generates a random valid input; what `size` counts (lines, grid side...) depends on the
+    /// day.
+    fn generate(rng: &mut StdRng, size: usize) -> String;
+    /// Statistics about the parsed input, as (name, value), shown by the REPL's `stats` command.
+    fn stats(_input: &Self::Input) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+    /// Answers the query named `name` of [Solution::QUERIES], with its arguments.
+    fn query(_input: &Self::Input, name: &str, _args: &[&str]) -> Result<String, Box<dyn Error>> {
+        Err(format!("Unknown query `{}`", name).into())
+    }
+    /// The value of the parameter `name` of [Solution::PARAMS], as overridden by the runner or
+    /// else its default.
+    fn param<T>(name: &str) -> Result<T, Box<dyn Error>>
+    where
+        Self: Sized + 'static,
+        T: FromStr,
+        T::Err: Display,
+    {
+        params::get::<Self, T>(name)
+    }
+}
+
+/// A day-specific REPL command, e.g. `paths <from> <to>`.
+pub struct Query {
+    pub name: &'static str,
+    /// The arguments, e.g. `<from> <to>`
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+/// A named implementation of a part, e.g. a brute-force one, to cross-check the main one against.
+pub struct Alternative<I, O> {
+    pub name: &'static str,
+    pub run: fn(&I) -> Result<O, Box<dyn Error>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> u8 {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(u8::from(*self))
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, String> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("{:?} is not a part, expected 1 or 2", s)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "Part 1"),
+            Part::Two => write!(f, "Part 2"),
+        }
+    }
+}
+
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// Type-erased view of a [Solution], so that days with different input and output types can be
+/// stored side by side in a registry.
+pub trait AnySolution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn validate_input(&self, input: &str) -> Result<(), ValidationError>;
+    /// Validates, then parses the input
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, Box<dyn Error>>;
+    fn run_part(&self, input: &ParsedInput, part: Part) -> Result<String, Box<dyn Error>>;
+    fn stats(&self, input: &ParsedInput) -> Result<Vec<(&'static str, String)>, Box<dyn Error>>;
+    fn queries(&self) -> &'static [Query];
+    fn query(
+        &self,
+        input: &ParsedInput,
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, Box<dyn Error>>;
+    fn input_size(&self) -> usize;
+    /// Generates an input from `seed`, see [Solution::generate]
+    fn generate_input(&self, seed: u64, size: usize) -> String;
+    /// Names of the alternative implementations of a part
+    fn alternatives(&self, part: Part) -> Vec<&'static str>;
+    fn run_alternative(
+        &self,
+        input: &ParsedInput,
+        part: Part,
+        name: &str,
+    ) -> Result<String, Box<dyn Error>>;
+    fn params(&self) -> &'static [Param];
+    /// Overrides the values of some parameters, resetting the others to their default
+    fn set_params(&self, values: &[(String, String)]) -> Result<(), Box<dyn Error>>;
+}
+
+impl<S> AnySolution for S
+where
+    S: Solution + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn validate_input(&self, input: &str) -> Result<(), ValidationError> {
+        ValidationError::check(S::validate(input))
+    }
+
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, Box<dyn Error>> {
+        let _span = tracing::info_span!("parse", day = S::DAY).entered();
+        self.validate_input(input)?;
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn run_part(&self, input: &ParsedInput, part: Part) -> Result<String, Box<dyn Error>> {
+        let input = downcast_input::<S>(input)?;
+        let _span = part_span(S::DAY, part, MAIN_IMPLEMENTATION).entered();
+        match part {
+            Part::One => S::part_1(input).map(|res| res.to_string()),
+            Part::Two => S::part_2(input).map(|res| res.to_string()),
+        }
+    }
+
+    fn stats(&self, input: &ParsedInput) -> Result<Vec<(&'static str, String)>, Box<dyn Error>> {
+        Ok(S::stats(downcast_input::<S>(input)?))
+    }
+
+    fn queries(&self) -> &'static [Query] {
+        S::QUERIES
+    }
+
+    fn query(
+        &self,
+        input: &ParsedInput,
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, Box<dyn Error>> {
+        S::query(downcast_input::<S>(input)?, name, args)
+    }
+
+    fn input_size(&self) -> usize {
+        S::INPUT_SIZE
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> String {
+        generator::generate::<S>(seed, size)
+    }
+
+    fn alternatives(&self, part: Part) -> Vec<&'static str> {
+        match part {
+            Part::One => S::ALTERNATIVES_1.iter().map(|alt| alt.name).collect(),
+            Part::Two => S::ALTERNATIVES_2.iter().map(|alt| alt.name).collect(),
+        }
+    }
+
+    fn run_alternative(
+        &self,
+        input: &ParsedInput,
+        part: Part,
+        name: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        fn run_named<I, O: Display>(
+            alternatives: &[Alternative<I, O>],
+            input: &I,
+            name: &str,
+        ) -> Option<Result<String, Box<dyn Error>>> {
+            let alternative = alternatives.iter().find(|alt| alt.name == name)?;
+            Some((alternative.run)(input).map(|res| res.to_string()))
+        }
+
+        let input = downcast_input::<S>(input)?;
+        let _span = part_span(S::DAY, part, name).entered();
+        match part {
+            Part::One => run_named(S::ALTERNATIVES_1, input, name),
+            Part::Two => run_named(S::ALTERNATIVES_2, input, name),
+        }
+        .unwrap_or_else(|| {
+            Err(format!("Day {} {} has no implementation {:?}", S::DAY, part, name).into())
+        })
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn set_params(&self, values: &[(String, String)]) -> Result<(), Box<dyn Error>> {
+        params::set::<S>(values)
+    }
+}
+
+/// Name of the implementation of a part given by [Solution::part_1] or [Solution::part_2], which
+/// the alternatives must not use
+pub const MAIN_IMPLEMENTATION: &str = "main";
+
+/// The span of a run of a part, `part_1` or `part_2`.
+fn part_span(day: u8, part: Part, implementation: &str) -> tracing::Span {
+    match part {
+        Part::One => tracing::info_span!("part_1", day, implementation),
+        Part::Two => tracing::info_span!("part_2", day, implementation),
+    }
+}
+
+fn downcast_input<S: Solution>(input: &ParsedInput) -> Result<&S::Input, String> {
+    input
+        .downcast_ref::<S::Input>()
+        .ok_or(format!("Input was not parsed by day {}", S::DAY))
+}
+
+/// Entry point for a day's own binary: reads the input (from the file given with `--input <path>`,
+/// `--input -` for stdin, or else the default input directory), then solves and times both parts.
+pub fn run<S: Solution>() -> Result<(), Box<dyn Error>> {
+    crate::init_logging();
+    let args = env::args().skip(1).collect_vec();
+    let source = match args.as_slice() {
+        [] => InputSource::default_dir(),
+        [flag, path] if flag == "--input" => InputSource::from_arg(path),
+        _ => return Err("Usage: [--input <path>|-]".into()),
+    };
+    let input = source.read(S::DAY)?;
+    let input = tracing::info_span!("parse", day = S::DAY).in_scope(|| {
+        ValidationError::check(S::validate(&input))?;
+        Ok::<_, Box<dyn Error>>(S::parse(&input)?)
+    })?;
+
+    let part_1 = part_span(S::DAY, Part::One, MAIN_IMPLEMENTATION)
+        .in_scope(|| measure(S::DAY, Part::One, || S::part_1(&input)));
+    println!("{}", OutputFormat::Human.format(&part_1));
+
+    let part_2 = part_span(S::DAY, Part::Two, MAIN_IMPLEMENTATION)
+        .in_scope(|| measure(S::DAY, Part::Two, || S::part_2(&input)));
+    println!("{}", OutputFormat::Human.format(&part_2));
+
+    if part_1.is_ok() && part_2.is_ok() {
+        Ok(())
+    } else {
+        Err("Failed to solve the puzzle".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Line;
+    use rand::Rng;
+
+    struct Dummy;
+
+    impl Solution for Dummy {
+        const DAY: u8 = 42;
+        const TITLE: &'static str = "Dummy";
+        const INPUT_SIZE: usize = 3;
+
+        type Input = Vec<u32>;
+        type Output1 = u32;
+        type Output2 = usize;
+
+        fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+            let line = Line { number: 1, text: input };
+            line.parse_separated(input, ',', "a number")
+        }
+
+        fn part_1(input: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+            Ok(input.iter().sum())
+        }
+
+        fn part_2(input: &Vec<u32>) -> Result<usize, Box<dyn Error>> {
+            Ok(input.len())
+        }
+
+        fn generate(rng: &mut StdRng, size: usize) -> String {
+            (0..size.max(1))
+                .map(|_| rng.random_range(0..100).to_string())
+                .join(",")
+        }
+
+        const ALTERNATIVES_1: &'static [Alternative<Vec<u32>, u32>] =
+            &[Alternative { name: "reversed", run: |input| Ok(input.iter().rev().sum()) }];
+
+        const PARAMS: &'static [Param] = &[Param { name: "factor", default: "1", help: "" }];
+    }
+
+    #[test]
+    fn test_any_solution() {
+        let solution: &dyn AnySolution = &Dummy;
+        let input = solution.parse_input("1,2,3").unwrap();
+        assert_eq!(solution.day(), 42);
+        assert_eq!(solution.run_part(&input, Part::One).unwrap(), "6");
+        assert_eq!(solution.run_part(&input, Part::Two).unwrap(), "3");
+    }
+
+    #[test]
+    fn test_params() {
+        let solution: &dyn AnySolution = &Dummy;
+        assert_eq!(Dummy::param::<u32>("factor").unwrap(), 1);
+        solution
+            .set_params(&[("factor".to_string(), "x".to_string())])
+            .unwrap();
+        assert!(Dummy::param::<u32>("factor").is_err());
+        solution
+            .set_params(&[("factor".to_string(), "3".to_string())])
+            .unwrap();
+        assert_eq!(Dummy::param::<u32>("factor").unwrap(), 3);
+        assert!(Dummy::param::<u32>("offset").is_err());
+        assert!(
+            solution
+                .set_params(&[("offset".to_string(), "3".to_string())])
+                .is_err()
+        );
+        solution.set_params(&[]).unwrap();
+        assert_eq!(Dummy::param::<u32>("factor").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_spans() {
+        use std::io;
+        use std::sync::{Arc, Mutex};
+        use tracing_subscriber::fmt::format::FmtSpan;
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_span_events(FmtSpan::CLOSE)
+            .with_ansi(false)
+            .with_target(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let solution: &dyn AnySolution = &Dummy;
+            let input = solution.parse_input("1,2,3").unwrap();
+            solution.run_part(&input, Part::Two).unwrap();
+        });
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("parse{day=42}: close"));
+        assert!(output.contains("part_2{day=42 implementation=\"main\"}: close"));
+    }
+
+    #[test]
+    fn test_generate() {
+        crate::check_generator::<Dummy>(10);
+        let solution: &dyn AnySolution = &Dummy;
+        let input = solution.generate_input(7, 10);
+        assert_eq!(input.split(',').count(), 10);
+        assert_eq!(solution.generate_input(7, 10), input);
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let solution: &dyn AnySolution = &Dummy;
+        let input = solution.parse_input("1,2,3").unwrap();
+        assert_eq!(solution.alternatives(Part::One), ["reversed"]);
+        assert!(solution.alternatives(Part::Two).is_empty());
+        assert_eq!(
+            solution
+                .run_alternative(&input, Part::One, "reversed")
+                .unwrap(),
+            "6"
+        );
+        assert!(
+            solution
+                .run_alternative(&input, Part::Two, "reversed")
+                .is_err()
+        );
+    }
+}
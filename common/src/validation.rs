@@ -0,0 +1,76 @@
+use crate::parse_error::{Line, ParseError, lines};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// All the problems found in an input by [`Solution::validate`](crate::Solution::validate).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    /// In input order
+    pub errors: Vec<ParseError>,
+}
+
+impl ValidationError {
+    /// Fails if there are any errors.
+    pub fn check(mut errors: Vec<ParseError>) -> Result<(), ValidationError> {
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            errors.sort_by_key(|e| (e.line, e.column));
+            Err(ValidationError { errors })
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} problem(s) in the input:", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "\n{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Parses every line with `parse_line`, keeping going after errors. Returns the parsed lines, and
+/// the errors.
+pub fn parse_each_line<'a, T>(
+    input: &'a str,
+    parse_line: impl Fn(&Line<'a>) -> Result<T, ParseError>,
+) -> (Vec<(Line<'a>, T)>, Vec<ParseError>) {
+    let mut parsed = Vec::new();
+    let mut errors = Vec::new();
+    for line in lines(input) {
+        match parse_line(&line) {
+            Ok(value) => parsed.push((line, value)),
+            Err(e) => errors.push(e),
+        }
+    }
+    (parsed, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_each_line() {
+        let (parsed, errors) = parse_each_line("1\nx\n3\ny", |line| {
+            line.parse_at::<u32>(line.text, "a number")
+        });
+        assert_eq!(parsed.iter().map(|(_, n)| *n).collect::<Vec<_>>(), [1, 3]);
+        let error = ValidationError::check(errors).unwrap_err();
+        assert_eq!(
+            error.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            [2, 4]
+        );
+        assert!(
+            error
+                .to_string()
+                .starts_with("2 problem(s) in the input:\n2:1: expected")
+        );
+        assert_eq!(ValidationError::check(Vec::new()), Ok(()));
+    }
+}
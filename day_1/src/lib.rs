@@ -0,0 +1,219 @@
+//! Day 1: Secret Entrance, counting how many times a dial pointing at 0..=99 ends up at 0.
+
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Alternative, Line, ParseError, Solution, lines, parse_each_line};
+use std::error::Error;
+
+/// The [Solution] of day 1, on a list of rotations.
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Secret Entrance";
+    const INPUT_SIZE: usize = 4800;
+
+    type Input = Vec<i64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    const ALTERNATIVES_1: &'static [Alternative<Vec<i64>, u64>] = &[Alternative {
+        name: "brute force",
+        run: |rotations| Ok(count_zeros_click_by_click(rotations).0),
+    }];
+    const ALTERNATIVES_2: &'static [Alternative<Vec<i64>, u64>] = &[Alternative {
+        name: "brute force",
+        run: |rotations| Ok(count_zeros_click_by_click(rotations).1),
+    }];
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse_rotations(input)
+    }
+
+    fn validate(input: &str) -> Vec<ParseError> {
+        parse_each_line(input, parse_rotation).1
+    }
+
+    fn part_1(rotations: &Vec<i64>) -> Result<u64, Box<dyn Error>> {
+        Ok(part_1(rotations))
+    }
+
+    fn part_2(rotations: &Vec<i64>) -> Result<u64, Box<dyn Error>> {
+        Ok(part_2(rotations))
+    }
+
+    /// `size` rotations.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(rotations: &Vec<i64>) -> Vec<(&'static str, String)> {
+        let left = rotations.iter().filter(|r| **r < 0).count();
+        let clicks = rotations.iter().map(|r| r.unsigned_abs()).sum::<u64>();
+        vec![
+            ("rotations", rotations.len().to_string()),
+            (
+                "left / right",
+                format!("{} / {}", left, rotations.len() - left),
+            ),
+            ("clicks", clicks.to_string()),
+        ]
+    }
+}
+
+/// Number of rotations after which the dial, starting at 50, points at 0.
+pub fn part_1(rotations: &[i64]) -> u64 {
+    let mut password = 0;
+    let mut position = 50;
+
+    for rotation in rotations {
+        let effective_rotation = rotation % 100;
+        let raw_position = position + effective_rotation;
+        position = if raw_position < 0 {
+            100 + raw_position
+        } else if raw_position > 99 {
+            raw_position - 100
+        } else {
+            raw_position
+        };
+        if position == 0 {
+            password += 1;
+        }
+    }
+
+    password
+}
+
+/// Number of clicks after which the dial, starting at 50, points at 0.
+pub fn part_2(rotations: &[i64]) -> u64 {
+    let mut password = 0;
+    let mut position = 50;
+
+    for rotation in rotations {
+        // Count all full rotations first, since they simply bring us back to the same position
+        password += (rotation / 100).unsigned_abs();
+
+        // Apply remaining clicks
+        let starting_position = position;
+        let effective_rotation = rotation % 100;
+        if effective_rotation != 0 {
+            let raw_position = starting_position + effective_rotation;
+            position = if raw_position < 0 {
+                100 + raw_position
+            } else if raw_position > 99 {
+                raw_position - 100
+            } else {
+                raw_position
+            };
+
+            // Count if the remaining clicks included 0. There is one subtle case: do not count when
+            // going left _from zero_ (that zero was already counted on the previous rotation)
+            if (starting_position != 0 && raw_position <= 0) || raw_position >= 100 {
+                password += 1;
+            }
+        }
+    }
+
+    password
+}
+
+/// Turns the dial one click at a time, and counts how many times it points at 0 after a rotation,
+/// and after any click.
+pub fn count_zeros_click_by_click(rotations: &[i64]) -> (u64, u64) {
+    let mut after_rotation = 0;
+    let mut after_click = 0;
+    let mut position = 50;
+
+    for rotation in rotations {
+        for _ in 0..rotation.unsigned_abs() {
+            position = (position + rotation.signum()).rem_euclid(100);
+            if position == 0 {
+                after_click += 1;
+            }
+        }
+        if position == 0 {
+            after_rotation += 1;
+        }
+    }
+
+    (after_rotation, after_click)
+}
+
+/// Parses one rotation per line, like `L68`: a number of clicks, negative to the left.
+pub fn parse_rotations(input: &str) -> Result<Vec<i64>, ParseError> {
+    lines(input).map(|line| parse_rotation(&line)).collect()
+}
+
+fn parse_rotation(line: &Line) -> Result<i64, ParseError> {
+    let direction = match line.text.chars().next() {
+        Some('R') => 1,
+        Some('L') => -1,
+        _ => return Err(line.error_at_char(0, "a direction, `L` or `R`")),
+    };
+    // Unsigned, so that `L-5` is an error and the rotations can't overflow
+    let count: u32 = line.parse_at(&line.text[1..], "a number of clicks")?;
+    Ok(direction * i64::from(count))
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if rng.random_bool(0.5) { 'L' } else { 'R' };
+            format!("{}{}\n", direction, rng.random_range(1..1000))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::proptest::collection::vec;
+    use common::proptest::prelude::*;
+
+    common::example_tests! {
+        Day1,
+        ex1: { part_1: 3, part_2: 6 },
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_rotations("L68\nR1x\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 2, "1x")
+        );
+        let error = parse_rotations("L68\n\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 1, "")
+        );
+        assert!(parse_rotations("R-9223372036854775808\n").is_err());
+        assert!(parse_rotations("é\n").is_err());
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        // Including rotations of 0, and whole turns from and to 0
+        let rotations = || {
+            vec(
+                prop_oneof![-300i64..=300, (-3i64..=3).prop_map(|t| 100 * t)],
+                0..20,
+            )
+        };
+        common::check_against_reference(
+            rotations(),
+            |rotations| count_zeros_click_by_click(rotations).0,
+            |rotations| part_1(rotations),
+        );
+        common::check_against_reference(
+            rotations(),
+            |rotations| count_zeros_click_by_click(rotations).1,
+            |rotations| part_2(rotations),
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day1>(100);
+    }
+}
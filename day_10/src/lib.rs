@@ -0,0 +1,468 @@
+//! Day 10: Factory, pressing the fewest buttons to configure the machines.
+
+use common::itertools::Itertools;
+use common::maplit::hashset;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::rand::seq::index;
+use common::{
+    Alternative, Line, ParseError, Query, Solution, check_cancelled, lines, parse_each_line,
+    progress,
+};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use z3::ast::Int;
+use z3::{Optimize, SatResult};
+
+/// The [Solution] of day 10, on a list of machines.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Factory";
+    const INPUT_SIZE: usize = 200;
+
+    type Input = Vec<Machine>;
+    type Output1 = usize;
+    type Output2 = u64;
+
+    const ALTERNATIVES_2: &'static [Alternative<Vec<Machine>, u64>] =
+        &[Alternative { name: "native", run: |machines| part_2_native(machines) }];
+
+    fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+        parse_input(input)
+    }
+
+    /// The buttons must only toggle existing lights, and there must be a joltage per light.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let (machines, mut errors) = parse_each_line(input, parse_machine);
+        for (line, machine) in machines {
+            let light_count = machine.lights.len();
+            // Already parsed, so the tokens are well-formed
+            let tokens = line.text.split_whitespace().collect_vec();
+            for button in &tokens[1..tokens.len() - 1] {
+                for index in button[1..button.len() - 1].split(',') {
+                    if index.parse::<usize>().is_ok_and(|i| i >= light_count) {
+                        let expected = format!("a light index below {}", light_count);
+                        errors.push(line.error_at(index, expected));
+                    }
+                }
+            }
+            if machine.joltages.len() != light_count {
+                let expected = format!("{} joltages, one per light", light_count);
+                errors.push(line.error_at(tokens[tokens.len() - 1], expected));
+            }
+        }
+        errors
+    }
+
+    fn part_1(machines: &Vec<Machine>) -> Result<usize, Box<dyn Error>> {
+        part_1(machines)
+    }
+
+    fn part_2(machines: &Vec<Machine>) -> Result<u64, Box<dyn Error>> {
+        part_2(machines)
+    }
+
+    /// `size` machines.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(machines: &Vec<Machine>) -> Vec<(&'static str, String)> {
+        let max = |count: fn(&Machine) -> usize| machines.iter().map(count).max().unwrap_or(0);
+        vec![
+            ("machines", machines.len().to_string()),
+            ("max lights", max(|m| m.lights.len()).to_string()),
+            ("max buttons", max(|m| m.buttons.len()).to_string()),
+            (
+                "max joltage",
+                machines
+                    .iter()
+                    .flat_map(|m| &m.joltages)
+                    .max()
+                    .unwrap_or(&0)
+                    .to_string(),
+            ),
+        ]
+    }
+
+    const QUERIES: &'static [Query] = &[Query {
+        name: "machine",
+        args: "<n>",
+        help: "a machine (from 1), as parsed",
+    }];
+
+    fn query(machines: &Vec<Machine>, name: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        let [n] = args else {
+            return Err(format!("Usage: {} <n>", name).into());
+        };
+        let machine = n
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| machines.get(n.checked_sub(1)?))
+            .ok_or(format!("No machine {}", n))?;
+        Ok(format!("{:?}", machine))
+    }
+}
+
+/// Fewest button presses to turn on the right lights of every machine.
+pub fn part_1(machines: &[Machine]) -> Result<usize, Box<dyn Error>> {
+    machines
+        .iter()
+        .enumerate()
+        .map(|(i, machine)| {
+            let mut lights_states = hashset! { vec![false; machine.lights.len()] };
+            let mut seen_states = lights_states.clone();
+            let mut btn_presses = 0;
+            while !lights_states.contains(&machine.lights) {
+                check_cancelled()?;
+                let mut new_states = HashSet::new();
+                for prev_state in lights_states.iter() {
+                    for button in machine.buttons.iter() {
+                        let mut new_state = prev_state.clone();
+                        for btn_i in button.iter() {
+                            new_state[*btn_i] = !new_state[*btn_i];
+                        }
+                        if seen_states.insert(new_state.clone()) {
+                            new_states.insert(new_state);
+                        }
+                    }
+                }
+                if new_states.is_empty() {
+                    return Err(format!("Machine {} cannot turn on its lights", i + 1).into());
+                }
+                lights_states = new_states;
+                btn_presses += 1;
+            }
+            Ok(btn_presses)
+        })
+        .sum()
+}
+
+/// Fewest button presses to reach the joltages of every machine, with z3.
+pub fn part_2(machines: &[Machine]) -> Result<u64, Box<dyn Error>> {
+    progress::total(machines.len());
+    machines
+        .iter()
+        .enumerate()
+        .map(|(i, machine)| {
+            let optimize = Optimize::new();
+            let btn_counts = machine
+                .buttons
+                .iter()
+                .enumerate()
+                .map(|(i, _)| Int::fresh_const(&format!("btn_count_{}", i)))
+                .collect_vec();
+            for btn_count in btn_counts.iter() {
+                optimize.assert(&btn_count.ge(0));
+            }
+            for i in 0..machine.joltages.len() {
+                let sum = machine
+                    .buttons
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(btn_idx, btn)| {
+                        if btn.contains(&i) {
+                            Some(&btn_counts[btn_idx])
+                        } else {
+                            None
+                        }
+                    })
+                    .fold(Int::from_u64(0), |acc, x| acc + x);
+                optimize.assert(&sum.eq(machine.joltages[i]))
+            }
+            let total_sum = btn_counts.iter().fold(Int::from_u64(0), |acc, x| acc + x);
+            optimize.minimize(&total_sum);
+            if optimize.check(&[]) != SatResult::Sat {
+                return Err(format!("Machine {} cannot reach its joltages", i + 1).into());
+            }
+            let presses = optimize
+                .get_model()
+                .and_then(|model| model.eval(&total_sum, true))
+                .and_then(|presses| presses.as_u64())
+                .ok_or("z3 found no number of presses")?;
+            progress::advance(1);
+            Ok(presses)
+        })
+        .sum()
+}
+
+/// Solves part 2 without z3. Pressing a button `n` times is pressing it `n % 2` times, then twice
+/// `n / 2` times: the buttons pressed once must leave even joltages, and the fewest presses for
+/// the rest are twice the fewest presses for half of the remaining joltages.
+pub fn part_2_native(machines: &[Machine]) -> Result<u64, Box<dyn Error>> {
+    progress::total(machines.len());
+    machines
+        .iter()
+        .enumerate()
+        .map(|(i, machine)| {
+            check_cancelled()?;
+            // Joltage increments and number of presses of each combination of buttons pressed once
+            let combinations = (0..machine.buttons.len())
+                .powerset()
+                .map(|buttons| {
+                    let mut increments = vec![0; machine.joltages.len()];
+                    for light in buttons.iter().flat_map(|&button| &machine.buttons[button]) {
+                        increments[*light] += 1;
+                    }
+                    (increments, buttons.len() as u64)
+                })
+                .collect_vec();
+            let presses = fewest_presses(&machine.joltages, &combinations, &mut HashMap::new())
+                .ok_or_else(|| format!("Machine {} cannot reach its joltages", i + 1))?;
+            progress::advance(1);
+            Ok(presses)
+        })
+        .sum()
+}
+
+fn fewest_presses(
+    joltages: &[u32],
+    combinations: &[(Vec<u32>, u64)],
+    cache: &mut HashMap<Vec<u32>, Option<u64>>,
+) -> Option<u64> {
+    if joltages.iter().all(|&joltage| joltage == 0) {
+        return Some(0);
+    }
+    if let Some(&presses) = cache.get(joltages) {
+        return presses;
+    }
+    let presses = combinations
+        .iter()
+        .filter_map(|(increments, count)| {
+            let halves = joltages
+                .iter()
+                .zip(increments)
+                .map(|(&joltage, &increment)| {
+                    joltage
+                        .checked_sub(increment)
+                        .filter(|rest| rest % 2 == 0)
+                        .map(|rest| rest / 2)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(count + 2 * fewest_presses(&halves, combinations, cache)?)
+        })
+        .min();
+    cache.insert(joltages.to_vec(), presses);
+    presses
+}
+
+/// Parses a machine per line, like `[.##.] (3) (1,3) {3,5,4,7}`.
+pub fn parse_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    lines(input)
+        .map(|line| {
+            let Machine { lights, buttons, joltages } = parse_machine(&line)?;
+            Machine::new(lights, buttons, joltages).map_err(|e| line.error(e.expected))
+        })
+        .collect()
+}
+
+/// Parses the machine on `line`, without checking its buttons and joltages against its lights.
+fn parse_machine(line: &Line) -> Result<Machine, ParseError> {
+    let tokens = line.text.split_whitespace().collect_vec();
+    let [diagram, buttons @ .., joltages] = tokens.as_slice() else {
+        return Err(line.error("a machine like `[.##.] (3) (1,3) {3,5,4,7}`"));
+    };
+
+    let diagram = delimited(line, diagram, ('[', ']'), "a light diagram like `[.##.]`")?;
+    let lights = diagram
+        .char_indices()
+        .map(|(i, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(line.error_at(&diagram[i..i + c.len_utf8()], "a light, `.` or `#`")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let buttons = buttons
+        .iter()
+        .map(|button| {
+            let list = delimited(line, button, ('(', ')'), "a button like `(1,3)`")?;
+            line.parse_separated(list, ',', "a light index")
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let joltages = delimited(
+        line,
+        joltages,
+        ('{', '}'),
+        "joltage requirements like `{3,5,4,7}`",
+    )?;
+    let joltages = line.parse_separated(joltages, ',', "a joltage")?;
+
+    Ok(Machine { lights, buttons, joltages })
+}
+
+fn delimited<'a>(
+    line: &Line,
+    s: &'a str,
+    (open, close): (char, char),
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    s.strip_prefix(open)
+        .and_then(|s| s.strip_suffix(close))
+        .ok_or_else(|| line.error_at(s, expected))
+}
+
+/// The lights and joltages are those of random button presses, so that they can be reached.
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let light_count = rng.random_range(3..=10);
+            let buttons = (0..rng.random_range(3..=13))
+                .map(|_| {
+                    let amount = rng.random_range(1..=light_count);
+                    let mut lights = index::sample(rng, light_count, amount).into_vec();
+                    lights.sort();
+                    lights
+                })
+                .collect_vec();
+            let mut lights = vec![false; light_count];
+            let mut joltages = vec![0; light_count];
+            for button in &buttons {
+                let toggled = rng.random_bool(0.5);
+                let presses = rng.random_range(0..=20);
+                for &light in button {
+                    lights[light] ^= toggled;
+                    joltages[light] += presses;
+                }
+            }
+            format!(
+                "[{}] {} {{{}}}\n",
+                lights.iter().map(|&on| if on { '#' } else { '.' }).join(""),
+                buttons
+                    .iter()
+                    .map(|button| format!("({})", button.iter().join(",")))
+                    .join(" "),
+                joltages.iter().join(",")
+            )
+        })
+        .collect()
+}
+
+/// A machine, with as many lights as joltage counters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Machine {
+    /// The lights to turn on
+    lights: Vec<bool>,
+    /// The indices of the lights toggled (or counters increased) by each button
+    buttons: Vec<Vec<usize>>,
+    joltages: Vec<u32>,
+}
+
+impl Machine {
+    /// The machine with these lights to turn on, buttons and joltages, which must only toggle
+    /// existing lights and have a joltage per light.
+    pub fn new(
+        lights: Vec<bool>,
+        buttons: Vec<Vec<usize>>,
+        joltages: Vec<u32>,
+    ) -> Result<Machine, ParseError> {
+        let light_count = lights.len();
+        let expected = if buttons.iter().flatten().any(|&i| i >= light_count) {
+            format!("light indices below {}", light_count)
+        } else if joltages.len() != light_count {
+            format!("{} joltages, one per light", light_count)
+        } else {
+            return Ok(Machine { lights, buttons, joltages });
+        };
+        let text = format!(
+            "[{}] {} {{{}}}",
+            lights.iter().map(|&on| if on { '#' } else { '.' }).join(""),
+            buttons
+                .iter()
+                .map(|button| format!("({})", button.iter().join(",")))
+                .join(" "),
+            joltages.iter().join(",")
+        );
+        Err(ParseError::in_value(&text, expected))
+    }
+    pub fn lights(&self) -> &[bool] {
+        &self.lights
+    }
+    pub fn buttons(&self) -> &[Vec<usize>] {
+        &self.buttons
+    }
+    pub fn joltages(&self) -> &[u32] {
+        &self.joltages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::proptest::collection::vec;
+    use common::proptest::prelude::*;
+
+    common::example_tests! {
+        Day10,
+        ex1: { part_1: 7, part_2: 33 },
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day10::validate("[.#] (0,2) (1) {3,5,4}\n[.#] (0) x {1}\n");
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.line, e.snippet.as_str()))
+                .collect_vec(),
+            [(2, "x"), (1, "2"), (1, "{3,5,4}")]
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        let error = parse_input("[.#] (0,2) (1) {3,5}\n").unwrap_err();
+        assert_eq!(error.expected, "light indices below 2");
+        let error = Machine::new(vec![true], vec![vec![0]], vec![]).unwrap_err();
+        assert_eq!(
+            (error.snippet.as_str(), error.expected.as_str()),
+            ("[#] (0) {}", "1 joltages, one per light")
+        );
+    }
+
+    #[test]
+    fn test_unreachable_lights() {
+        let machines = parse_input("[#.] (0,1) {1,1}\n").unwrap();
+        assert!(part_1(&machines).is_err());
+        assert!(part_1(&parse_input("[#] {1}\n").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        // Pressing a button twice changes nothing, so it's the smallest set of buttons to press
+        fn brute_force(machines: &[Machine]) -> Option<usize> {
+            machines
+                .iter()
+                .map(|machine| {
+                    machine.buttons.iter().powerset().find_map(|buttons| {
+                        let mut lights = vec![false; machine.lights.len()];
+                        buttons
+                            .iter()
+                            .flat_map(|b| b.iter())
+                            .for_each(|&l| lights[l] ^= true);
+                        (lights == machine.lights).then_some(buttons.len())
+                    })
+                })
+                .sum()
+        }
+
+        let machine = (1usize..6).prop_flat_map(|light_count| {
+            let button = vec(0..light_count, 1..=light_count);
+            (vec(any::<bool>(), light_count), vec(button, 0..6)).prop_map(|(lights, buttons)| {
+                let joltages = vec![0; lights.len()];
+                Machine { lights, buttons, joltages }
+            })
+        });
+        common::check_against_reference(
+            vec(machine, 1..3),
+            |machines| brute_force(machines),
+            |machines| part_1(machines).ok(),
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day10>(20);
+    }
+}
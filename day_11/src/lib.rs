@@ -0,0 +1,346 @@
+//! Day 11: Reactor, counting the paths between devices.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::rand::seq::{SliceRandom, index};
+use common::{
+    Cancelled, Line, ParseError, Query, Solution, check_cancelled, lines, parse_each_line,
+};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// The [Solution] of day 11, on the outputs of each device.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Reactor";
+    const INPUT_SIZE: usize = 600;
+
+    type Input = Devices;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Devices, ParseError> {
+        parse_input(input)
+    }
+
+    /// Each device must be defined once, and the outputs must not loop back, or the paths
+    /// would be endless.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let (devices, mut errors) = parse_each_line(input, parse_device);
+        let mut lines_by_name = HashMap::new();
+        for (line, (name, _)) in &devices {
+            if lines_by_name.insert(name.as_str(), *line).is_some() {
+                errors.push(line.error_at(&line.text[..name.len()], "a device defined once"));
+            }
+        }
+
+        // Depth-first search, looking for an output which is still being explored
+        fn find_loop<'a>(
+            devices: &'a HashMap<&str, &Vec<String>>,
+            done: &mut HashSet<&'a str>,
+            path: &mut Vec<&'a str>,
+            curr: &'a str,
+        ) -> Option<(&'a str, &'a str)> {
+            if done.contains(curr) {
+                return None;
+            }
+            path.push(curr);
+            for out in devices.get(curr).into_iter().flat_map(|outs| outs.iter()) {
+                if path.contains(&out.as_str()) {
+                    return Some((curr, out));
+                }
+                if let Some(found) = find_loop(devices, done, path, out) {
+                    return Some(found);
+                }
+            }
+            path.pop();
+            done.insert(curr);
+            None
+        }
+
+        let outputs = devices
+            .iter()
+            .map(|(_, (name, outs))| (name.as_str(), outs))
+            .collect::<HashMap<_, _>>();
+        let mut done = HashSet::new();
+        for (name, _) in devices.iter().map(|(_, device)| device) {
+            if let Some((from, to)) = find_loop(&outputs, &mut done, &mut Vec::new(), name) {
+                let line = lines_by_name[from];
+                let snippet = line.text.split_whitespace().skip(1).find(|out| *out == to);
+                let expected = format!("an output which doesn't lead back to `{}`", to);
+                errors.push(line.error_at(snippet.unwrap_or(line.text), expected));
+                // The other devices of the loop would report it again
+                break;
+            }
+        }
+        errors
+    }
+
+    fn part_1(devices: &Devices) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(devices)?)
+    }
+
+    fn part_2(devices: &Devices) -> Result<usize, Box<dyn Error>> {
+        part_2(devices)
+    }
+
+    /// About `size` devices.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(devices: &Devices) -> Vec<(&'static str, String)> {
+        let connections = devices.values().map(|outputs| outputs.len()).sum::<usize>();
+        vec![
+            ("devices", devices.len().to_string()),
+            ("connections", connections.to_string()),
+        ]
+    }
+
+    const QUERIES: &'static [Query] = &[
+        Query {
+            name: "outputs",
+            args: "<device>",
+            help: "outputs of a device",
+        },
+        Query {
+            name: "inputs",
+            args: "<device>",
+            help: "devices with this output",
+        },
+        Query {
+            name: "paths",
+            args: "<from> <to>",
+            help: "number of paths between two devices",
+        },
+    ];
+
+    fn query(devices: &Devices, name: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        match (name, args) {
+            ("outputs", [device]) => Ok(devices
+                .get(*device)
+                .ok_or(format!("No device `{}`", device))?
+                .join(" ")),
+            ("inputs", [device]) => Ok(devices
+                .iter()
+                .filter(|(_, outputs)| outputs.iter().any(|out| out == device))
+                .map(|(name, _)| name)
+                .sorted()
+                .join(" ")),
+            ("paths", [from, to]) => {
+                let count = count_paths(devices, &mut HashMap::new(), from, to);
+                Ok(count.ok_or(OVERFLOW)?.to_string())
+            }
+            _ => Err(format!("Unknown query `{}` or wrong arguments, type `help`", name).into()),
+        }
+    }
+}
+
+/// Number of paths from `you` to `out`.
+pub fn part_1(devices: &Devices) -> Result<usize, Cancelled> {
+    // A simple recursive implementation of DFS works fine here (but explodes on inputs with
+    // many paths, hence the cancellation)
+    fn recurs_dfs(devices: &Devices, curr: &str) -> Result<usize, Cancelled> {
+        check_cancelled()?;
+        if curr == "out" {
+            Ok(1)
+        } else {
+            devices
+                .get(curr)
+                .unwrap_or(&Vec::new())
+                .iter()
+                .map(|out| recurs_dfs(devices, out))
+                .sum()
+        }
+    }
+
+    recurs_dfs(devices, "you")
+}
+
+/// Number of paths from `svr` to `out` going through both `dac` and `fft`.
+pub fn part_2(devices: &Devices) -> Result<usize, Box<dyn Error>> {
+    // Basically the same recursive DFS, but with an added cache
+    fn recurs_dfs<'a>(
+        devices: &'a Devices,
+        cache: &mut HashMap<(&'a str, bool, bool), usize>,
+        curr: &'a str,
+        with_dac: bool,
+        with_fft: bool,
+    ) -> Option<usize> {
+        if curr == "out" {
+            Some(if with_dac && with_fft { 1 } else { 0 })
+        } else {
+            match cache.get(&(curr, with_dac, with_fft)) {
+                Some(res) => Some(*res),
+                None => {
+                    let with_dac = with_dac || curr == "dac";
+                    let with_fft = with_fft || curr == "fft";
+                    let res =
+                        devices
+                            .get(curr)
+                            .into_iter()
+                            .flatten()
+                            .try_fold(0usize, |sum, out| {
+                                sum.checked_add(recurs_dfs(
+                                    devices, cache, out, with_dac, with_fft,
+                                )?)
+                            })?;
+                    cache.insert((curr, with_dac, with_fft), res);
+                    Some(res)
+                }
+            }
+        }
+    }
+
+    let mut cache = HashMap::new();
+    Ok(recurs_dfs(devices, &mut cache, "svr", false, false).ok_or(OVERFLOW)?)
+}
+
+const OVERFLOW: &str = "The number of paths overflows";
+
+/// Number of paths from `curr` to `to`, with a cache of the numbers of paths from each device,
+/// or `None` if it overflows.
+pub fn count_paths<'a>(
+    devices: &'a Devices,
+    cache: &mut HashMap<&'a str, usize>,
+    curr: &'a str,
+    to: &str,
+) -> Option<usize> {
+    if curr == to {
+        return Some(1);
+    }
+    if let Some(&count) = cache.get(curr) {
+        return Some(count);
+    }
+    let count = devices
+        .get(curr)
+        .into_iter()
+        .flatten()
+        .try_fold(0usize, |sum, out| {
+            sum.checked_add(count_paths(devices, cache, out, to)?)
+        })?;
+    cache.insert(curr, count);
+    Some(count)
+}
+
+/// Parses a device per line, like `aaa: you hhh`.
+pub fn parse_input(input: &str) -> Result<Devices, ParseError> {
+    lines(input)
+        .map(|line| parse_device(&line))
+        .collect::<Result<Devices, _>>()
+}
+
+fn parse_device(line: &Line) -> Result<(String, Vec<String>), ParseError> {
+    let (name, list) = line
+        .text
+        .split_once(": ")
+        .ok_or_else(|| line.error("a device and its outputs like `aaa: you hhh`"))?;
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(line.error_at(name, "a device name"));
+    }
+    let outputs = list.split_whitespace().map(|d| d.to_owned()).collect_vec();
+    Ok((name.to_owned(), outputs))
+}
+
+/// Layers of `size / 12` devices, each one connected to some devices of the next layer, with
+/// paths from `svr` to `out` through `fft` then `dac`. The number of layers stays the same, so
+/// that the number of paths fits in a `usize`.
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    const LAYERS: usize = 12;
+    let width = (size / LAYERS).max(1);
+    let mut name_len = 3;
+    while 26usize.pow(name_len) < 4 * LAYERS * width {
+        name_len += 1;
+    }
+    let mut used = HashSet::from(["you", "out", "svr", "dac", "fft"].map(String::from));
+    let mut layers = (0..LAYERS)
+        .map(|_| {
+            (0..width)
+                .map(|_| {
+                    loop {
+                        let name = (0..name_len)
+                            .map(|_| char::from(b'a' + rng.random_range(0..26)))
+                            .collect::<String>();
+                        if used.insert(name.clone()) {
+                            break name;
+                        }
+                    }
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+    for (layer, name) in [(0, "svr"), (3, "fft"), (6, "you"), (8, "dac")] {
+        layers[layer][0] = name.to_string();
+    }
+    // One of the paths, through the devices above
+    let path = (0..LAYERS)
+        .map(|layer| match layer {
+            0 | 3 | 8 => 0,
+            _ => rng.random_range(0..width),
+        })
+        .collect_vec();
+
+    let mut lines = Vec::new();
+    for (layer, names) in layers.iter().enumerate() {
+        for (i, name) in names.iter().enumerate() {
+            let outputs = match layers.get(layer + 1) {
+                None => vec!["out"],
+                Some(next) => {
+                    let amount = rng.random_range(1..=width.min(3));
+                    let mut outputs = index::sample(rng, width, amount).into_vec();
+                    if i == path[layer] && !outputs.contains(&path[layer + 1]) {
+                        outputs[0] = path[layer + 1];
+                    }
+                    outputs.iter().map(|&j| next[j].as_str()).collect()
+                }
+            };
+            lines.push(format!("{}: {}\n", name, outputs.join(" ")));
+        }
+    }
+    lines.shuffle(rng);
+    lines.concat()
+}
+
+/// The outputs of each device, by name.
+pub type Devices = HashMap<String, Vec<String>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::example_tests! {
+        Day11,
+        ex1: { part_1: 5 },
+        ex2: { part_2: 2 },
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day11::validate("you: aaa\naaa: bbb out\nbbb: aaa\nyou: out\n");
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.line, e.snippet.as_str()))
+                .collect_vec(),
+            [(4, "you"), (3, "aaa")]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Each layer doubles the number of paths
+        let input = (0..70)
+            .map(|i| format!("a{i}: a{j} b{j}\nb{i}: a{j} b{j}\n", i = i, j = i + 1))
+            .collect::<String>();
+        let input = format!("svr: a0\n{}a70: fft\nfft: dac\ndac: out\n", input);
+        assert!(part_2(&parse_input(&input).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day11>(60);
+    }
+}
@@ -0,0 +1,91 @@
+//! Day 12: not solved yet, counts the lines of the input.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{ParseError, Solution, lines};
+use std::error::Error;
+
+/// The [Solution] of day 12, on the lines of the input.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Christmas Tree Farm";
+    const INPUT_SIZE: usize = 10;
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        // e.g. parse lines, pointing at the offending part of the line on errors
+        let _parsed_lines = lines(input)
+            .map(|line| {
+                let (n, s) = line
+                    .text
+                    .split_once(",")
+                    .ok_or_else(|| line.error("`<number>,<text>`"))?;
+                Ok((line.parse_at::<usize>(n, "a number")?, s))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        // e.g. read lines
+        Ok(input.lines().map(|line| line.to_owned()).collect_vec())
+    }
+
+    fn part_1(lines: &Vec<String>) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(lines))
+    }
+
+    fn part_2(lines: &Vec<String>) -> Result<usize, Box<dyn Error>> {
+        Ok(part_2(lines))
+    }
+
+    /// `size` lines.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+/// Number of lines, until the puzzle is solved.
+pub fn part_1<S: AsRef<str>>(lines: &[S]) -> usize {
+    lines.len()
+}
+
+/// Number of lines, until the puzzle is solved.
+pub fn part_2<S: AsRef<str>>(lines: &[S]) -> usize {
+    lines.len()
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let word = (0..rng.random_range(1..=10))
+                .map(|_| char::from(b'a' + rng.random_range(0..26)))
+                .collect::<String>();
+            format!("{},{}\n", rng.random_range(0..1000), word)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let test_input = ["A", "B"];
+        assert_eq!(part_1(&test_input), 2);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let test_input = ["A", "B"];
+        assert_eq!(part_2(&test_input), 2);
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day12>(20);
+    }
+}
@@ -0,0 +1,213 @@
+//! Day 2: Gift Shop, summing the invalid product IDs made of a repeated sequence of digits.
+
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Line, ParseError, Solution, check_cancelled, lines, progress};
+use std::error::Error;
+
+/// The [Solution] of day 2, on a list of product ID ranges.
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Gift Shop";
+    const INPUT_SIZE: usize = 40;
+
+    type Input = Vec<Range>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Range>, ParseError> {
+        parse_input(input)
+    }
+
+    fn validate(input: &str) -> Vec<ParseError> {
+        lines(input)
+            .flat_map(|line| {
+                line.text
+                    .split(",")
+                    .filter_map(move |range| parse_range(&line, range).err())
+            })
+            .collect()
+    }
+
+    fn part_1(product_ranges: &Vec<Range>) -> Result<u64, Box<dyn Error>> {
+        part_1(product_ranges)
+    }
+
+    fn part_2(product_ranges: &Vec<Range>) -> Result<u64, Box<dyn Error>> {
+        part_2(product_ranges)
+    }
+
+    /// `size` ID ranges, on a single line.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+/// Sum of the IDs made of a sequence of digits repeated twice.
+pub fn part_1(product_ranges: &[Range]) -> Result<u64, Box<dyn Error>> {
+    sum_invalid_ids(product_ranges, |id| {
+        let (x, y) = id.split_at(id.len() / 2);
+        x == y
+    })
+}
+
+/// Sum of the IDs made of a sequence of digits repeated at least twice.
+pub fn part_2(product_ranges: &[Range]) -> Result<u64, Box<dyn Error>> {
+    fn is_id_repeating(id: &str, chunk_size: usize) -> bool {
+        if !id.len().is_multiple_of(chunk_size) {
+            return false;
+        }
+        for i in 0..(id.len() / chunk_size - 1) {
+            let start_0 = i * chunk_size;
+            let end_0 = start_0 + chunk_size;
+            let start_1 = (i + 1) * chunk_size;
+            let end_1 = start_1 + chunk_size;
+            if id[start_0..end_0] != id[start_1..end_1] {
+                return false;
+            }
+        }
+        true
+    }
+
+    sum_invalid_ids(product_ranges, |id| {
+        let max_seq_length = id.len() / 2;
+        for chunk_size in 1..=max_seq_length {
+            if is_id_repeating(id, chunk_size) {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+const OVERFLOW: &str = "The sum of the invalid IDs overflows";
+
+/// Sum of the IDs of all the ranges for which `is_invalid` holds, given their decimal digits.
+pub fn sum_invalid_ids<P>(product_ranges: &[Range], is_invalid: P) -> Result<u64, Box<dyn Error>>
+where
+    P: Fn(&str) -> bool,
+{
+    progress::total(product_ranges.len());
+    product_ranges.iter().try_fold(0u64, |sum, range| {
+        let range_sum =
+            (range.start..=range.end).try_fold(0u64, |sum, id| -> Result<_, Box<dyn Error>> {
+                // Ranges can be huge, but polling every ID would be too slow
+                if id.is_multiple_of(4096) {
+                    check_cancelled()?;
+                }
+                let id = if is_invalid(&id.to_string()) { id } else { 0 };
+                Ok(sum.checked_add(id).ok_or(OVERFLOW)?)
+            })?;
+        progress::advance(1);
+        Ok(sum.checked_add(range_sum).ok_or(OVERFLOW)?)
+    })
+}
+
+/// Parses comma-separated ranges like `11-22`.
+pub fn parse_input(input: &str) -> Result<Vec<Range>, ParseError> {
+    lines(input)
+        .flat_map(|line| {
+            line.text
+                .split(",")
+                .map(move |range| parse_range(&line, range))
+        })
+        .collect()
+}
+
+/// Parses `range`, a slice of the line's text.
+fn parse_range<'a>(line: &Line<'a>, range: &'a str) -> Result<Range, ParseError> {
+    let (start, end) = range
+        .split_once("-")
+        .ok_or_else(|| line.error_at(range, "a range like `11-22`"))?;
+    let start = line.parse_at(start, "a product ID")?;
+    let end = line.parse_at(end, "a product ID")?;
+    Range::new(start, end).map_err(|e| line.error_at(range, e.expected))
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    let ranges = (0..size.max(1))
+        .map(|_| {
+            let start = rng.random_range(1..10_000_000_000u64);
+            format!("{}-{}", start, start + rng.random_range(0..100_000))
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{}\n", ranges)
+}
+
+/// A range of product IDs, bounds included.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Range {
+    start: u64,
+    end: u64,
+}
+
+impl Range {
+    /// The range from `start` to `end`, which must not be reversed: it would silently be empty.
+    pub fn new(start: u64, end: u64) -> Result<Range, ParseError> {
+        if start > end {
+            let text = format!("{}-{}", start, end);
+            return Err(ParseError::in_value(&text, "a range with start <= end"));
+        }
+        Ok(Range { start, end })
+    }
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::example_tests! {
+        Day2,
+        ex1: { part_1: 1227775554, part_2: 4174379265 },
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("11-22,95115,998-1012").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (1, 7, "95115")
+        );
+    }
+
+    #[test]
+    fn test_reversed_range() {
+        let error = parse_input("11-22,1012-998").unwrap_err();
+        assert_eq!((error.column, error.snippet.as_str()), (7, "1012-998"));
+        assert!(Range::new(5, 3).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day2::validate("11-22,95-x,1012-998");
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| e.snippet.as_str())
+                .collect::<Vec<_>>(),
+            ["x", "1012-998"]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let id = 11111111111111111111u64;
+        let ranges = [Range::new(id, id).unwrap(); 2];
+        assert_eq!(part_1(&ranges[..1]).unwrap(), id);
+        assert!(part_1(&ranges).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day2>(20);
+    }
+}
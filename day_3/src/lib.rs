@@ -0,0 +1,193 @@
+//! Day 3: Lobby, turning on the batteries of each bank to get the largest joltage.
+
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Line, ParseError, Solution, lines, parse_each_line};
+use std::error::Error;
+
+/// The [Solution] of day 3, on a list of battery banks.
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Lobby";
+    const INPUT_SIZE: usize = 200;
+
+    type Input = Vec<Vec<u64>>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn validate(input: &str) -> Vec<ParseError> {
+        parse_each_line(input, parse_bank).1
+    }
+
+    fn part_1(battery_banks: &Vec<Vec<u64>>) -> Result<u64, Box<dyn Error>> {
+        part_1(battery_banks)
+    }
+
+    fn part_2(battery_banks: &Vec<Vec<u64>>) -> Result<u64, Box<dyn Error>> {
+        part_2(battery_banks)
+    }
+
+    /// `size` banks of 100 batteries.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+/// Total joltage with 2 batteries turned on per bank.
+pub fn part_1(battery_banks: &[Vec<u64>]) -> Result<u64, Box<dyn Error>> {
+    max_joltage(battery_banks, 2)
+}
+
+/// Total joltage with 12 batteries turned on per bank.
+pub fn part_2(battery_banks: &[Vec<u64>]) -> Result<u64, Box<dyn Error>> {
+    max_joltage(battery_banks, 12)
+}
+
+/// Most batteries turned on per bank, so that a bank's joltage fits in a `u64`.
+pub const MAX_BATTERIES_PER_BANK: u32 = 19;
+
+/// Sum over the banks of the largest number made of `batteries_per_bank` of their digits, in
+/// order. Fails unless each bank has enough batteries, whose joltages are digits, and
+/// `batteries_per_bank` is between 1 and [MAX_BATTERIES_PER_BANK].
+pub fn max_joltage(
+    battery_banks: &[Vec<u64>],
+    batteries_per_bank: u32,
+) -> Result<u64, Box<dyn Error>> {
+    fn recurs(bank: &[u64], remaining: u32) -> u64 {
+        if remaining == 1 {
+            *bank.iter().max().unwrap()
+        } else {
+            // We always get the max total value by first searching for the max value for the most
+            // significant digit (while leaving as much search space as possible for the following
+            // digits, and in any case at least enough for the number of expected digits).
+            let digit_value = bank[0..(bank.len() - (remaining as usize) + 1)]
+                .iter()
+                .max()
+                .unwrap();
+            // max() returns the last occurrence of the max value, so we have to do a separate
+            // search for the position of its first occurrence.
+            let digit_pos = bank.iter().position(|d| d == digit_value).unwrap();
+            10u64.pow(remaining - 1) * digit_value + recurs(&bank[(digit_pos + 1)..], remaining - 1)
+        }
+    }
+    if !(1..=MAX_BATTERIES_PER_BANK).contains(&batteries_per_bank) {
+        return Err(format!(
+            "Cannot turn on {} batteries per bank, only 1 to {}",
+            batteries_per_bank, MAX_BATTERIES_PER_BANK
+        )
+        .into());
+    }
+    battery_banks
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |sum, (i, bank)| {
+            if bank.len() < batteries_per_bank as usize {
+                return Err(format!(
+                    "Bank {} has fewer than {} batteries",
+                    i + 1,
+                    batteries_per_bank
+                )
+                .into());
+            }
+            if bank.iter().any(|&joltage| joltage > 9) {
+                return Err(format!("Bank {} has a joltage above 9", i + 1).into());
+            }
+            sum.checked_add(recurs(bank, batteries_per_bank))
+                .ok_or_else(|| "The total joltage overflows".into())
+        })
+}
+
+/// Parses a bank per line, with a digit per battery.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    lines(input).map(|line| parse_bank(&line)).collect()
+}
+
+fn parse_bank(line: &Line) -> Result<Vec<u64>, ParseError> {
+    let bank = line
+        .text
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|d| d as u64)
+                .ok_or_else(|| line.error_at_char(i, "a battery joltage digit"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Part 2 turns on 12 batteries per bank
+    if bank.len() < 12 {
+        return Err(line.error("a bank of at least 12 batteries"));
+    }
+    Ok(bank)
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let bank = (0..100)
+                .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                .collect::<String>();
+            format!("{}\n", bank)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::itertools::Itertools;
+    use common::proptest::collection::vec;
+    use common::proptest::prelude::*;
+
+    common::example_tests! {
+        Day3,
+        ex1: { part_1: 357, part_2: 3121910778619 },
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        // The largest number among all the ways to pick `count` digits, for the greedy search
+        fn brute_force(banks: &[Vec<u64>], count: u32) -> u64 {
+            banks
+                .iter()
+                .map(|bank| {
+                    bank.iter()
+                        .combinations(count as usize)
+                        .map(|digits| digits.iter().fold(0, |n, &&d| 10 * n + d))
+                        .max()
+                        .unwrap()
+                })
+                .sum()
+        }
+
+        let inputs = (1u32..=6)
+            .prop_flat_map(|count| (vec(vec(0u64..10, count as usize..12), 1..4), Just(count)));
+        common::check_against_reference(
+            inputs,
+            |(banks, count)| brute_force(banks, *count),
+            |(banks, count)| max_joltage(banks, *count).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let banks = [vec![9; 20]];
+        assert_eq!(max_joltage(&banks, 19).unwrap(), 9999999999999999999);
+        assert!(max_joltage(&banks, 0).is_err());
+        assert!(max_joltage(&banks, 20).is_err());
+        assert!(max_joltage(&[vec![1, 2]], 3).is_err());
+        assert!(max_joltage(&[vec![]], 1).is_err());
+        assert!(max_joltage(&[vec![10, 2]], 1).is_err());
+        assert!(max_joltage(&[vec![9; 19], vec![9; 19]], 19).is_err());
+        assert_eq!(max_joltage(&[], 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day3>(20);
+    }
+}
@@ -0,0 +1,317 @@
+//! Day 4: Printing Department, finding the paper rolls a forklift can access.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::tracing;
+use common::{Line, ParseError, Query, Solution, lines, progress};
+use std::error::Error;
+
+/// The [Solution] of day 4, on a grid of paper rolls.
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Printing Department";
+    const INPUT_SIZE: usize = 135;
+
+    type Input = Warehouse;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Warehouse, ParseError> {
+        parse_input(input)
+    }
+
+    /// The grid must be a non-empty rectangle of `@` and `.`.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.len());
+        if width == 0 {
+            return vec![ParseError::at_end(input, "a grid of `@` and `.`")];
+        }
+        lines(input)
+            .flat_map(|line| {
+                let cells = line
+                    .text
+                    .char_indices()
+                    .filter(|(_, c)| !matches!(c, '@' | '.'))
+                    .map(move |(i, _)| {
+                        line.error_at_char(i, "a paper roll `@` or an empty space `.`")
+                    });
+                let row = (line.text.len() != width)
+                    .then(|| line.error(format!("a row of {} cells", width)));
+                cells.chain(row)
+            })
+            .collect()
+    }
+
+    fn part_1(warehouse: &Warehouse) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(warehouse))
+    }
+
+    fn part_2(warehouse: &Warehouse) -> Result<usize, Box<dyn Error>> {
+        Ok(part_2(warehouse))
+    }
+
+    /// A `size` x `size` grid.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(warehouse: &Warehouse) -> Vec<(&'static str, String)> {
+        let rolls = warehouse.iter_values().filter(|(_, roll)| *roll).count();
+        vec![
+            (
+                "grid",
+                format!("{} x {}", warehouse.x_max + 1, warehouse.y_max + 1),
+            ),
+            ("paper rolls", rolls.to_string()),
+        ]
+    }
+
+    const QUERIES: &'static [Query] = &[Query {
+        name: "cell",
+        args: "<x> <y>",
+        help: "content of a cell (from 0), and its neighboring paper rolls",
+    }];
+
+    fn query(warehouse: &Warehouse, name: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        match (name, args) {
+            ("cell", [x, y]) => {
+                let p = Point { x: x.parse()?, y: y.parse()? };
+                let Some(roll) = warehouse.get(&p) else {
+                    return Err(format!("({}, {}) is outside of the grid", p.x, p.y).into());
+                };
+                let neighbors = warehouse.neighbors(&p).filter(|(_, roll)| *roll).count();
+                let content = if roll { "paper roll" } else { "empty" };
+                Ok(format!(
+                    "{}, {} neighboring paper rolls",
+                    content, neighbors
+                ))
+            }
+            _ => Err(format!("Usage: {} <x> <y>", name).into()),
+        }
+    }
+}
+
+/// Number of rolls which are accessible right away.
+pub fn part_1(warehouse: &Warehouse) -> usize {
+    accessible_rolls(warehouse).len()
+}
+
+/// Number of rolls removed by removing the accessible ones until none is left.
+pub fn part_2(warehouse: &Warehouse) -> usize {
+    let mut warehouse = warehouse.clone();
+    let mut removed = 0;
+    let mut round = 0;
+
+    while let accessible = accessible_rolls(&warehouse)
+        && !accessible.is_empty()
+    {
+        for p in accessible.iter() {
+            warehouse.set(p, false);
+        }
+        removed += accessible.len();
+        round += 1;
+        tracing::debug!(
+            round,
+            removed = accessible.len(),
+            total = removed,
+            "removal round"
+        );
+        // The number of rounds is unknown until the end
+        progress::advance(1);
+        progress::message(format!("{} rolls removed", removed));
+    }
+
+    removed
+}
+
+/// The rolls with fewer than 4 rolls among their 8 neighbors.
+pub fn accessible_rolls(warehouse: &Warehouse) -> Vec<Point> {
+    warehouse
+        .iter_values()
+        .filter_map(|(p, is_occupied)| {
+            let is_accessible =
+                is_occupied && warehouse.neighbors(&p).filter(|(_, o)| *o).count() < 4;
+            if is_accessible { Some(p) } else { None }
+        })
+        .collect_vec()
+}
+
+/// Parses a grid of `.` and `@` (a roll), with lines of the same length.
+pub fn parse_input(input: &str) -> Result<Warehouse, ParseError> {
+    let paper_rolls = lines(input)
+        .map(|line| {
+            line.text
+                .char_indices()
+                .map(|(i, c)| match c {
+                    '@' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(line.error_at_char(i, "a paper roll `@` or an empty space `.`")),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Warehouse::new(paper_rolls)
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    let side = size.max(1);
+    (0..side)
+        .map(|_| {
+            let row = (0..side)
+                .map(|_| if rng.random_bool(0.6) { '@' } else { '.' })
+                .collect::<String>();
+            format!("{}\n", row)
+        })
+        .collect()
+}
+
+/// A position in the warehouse, from the top left corner.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Warehouse {
+    /// The warehouse with a roll where `paper_rolls` holds, by row then column, which must be a
+    /// non-empty rectangle. The errors point at the rows as they would be written in an input.
+    pub fn new(paper_rolls: Vec<Vec<bool>>) -> Result<Warehouse, ParseError> {
+        let row_text = |row: &[bool]| {
+            row.iter()
+                .map(|roll| if *roll { '@' } else { '.' })
+                .collect::<String>()
+        };
+        let width = paper_rolls.first().map_or(0, |row| row.len());
+        if width == 0 {
+            let text = paper_rolls
+                .iter()
+                .map(|row| row_text(row) + "\n")
+                .collect::<String>();
+            return Err(ParseError::at_end(&text, "a grid of `@` and `.`"));
+        }
+        if let Some((i, row)) = paper_rolls
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != width)
+        {
+            let text = row_text(row);
+            let line = Line { number: i + 1, text: &text };
+            return Err(line.error(format!("a row of {} cells", width)));
+        }
+
+        let y_max = paper_rolls.len() - 1;
+        let x_max = width - 1;
+        Ok(Warehouse { paper_rolls, y_max, x_max })
+    }
+    /// The largest column index.
+    pub fn x_max(&self) -> usize {
+        self.x_max
+    }
+    /// The largest row index.
+    pub fn y_max(&self) -> usize {
+        self.y_max
+    }
+    /// Whether there is a roll at `p`, or None if it is outside of the warehouse.
+    pub fn get(&self, p: &Point) -> Option<bool> {
+        self.paper_rolls.get(p.y)?.get(p.x).copied()
+    }
+    /// Whether there is a roll at `p`, which must be in the warehouse.
+    fn has_roll(&self, p: &Point) -> bool {
+        self.paper_rolls[p.y][p.x]
+    }
+    fn set(&mut self, p: &Point, value: bool) {
+        self.paper_rolls[p.y][p.x] = value;
+    }
+    /// All the positions, column by column.
+    pub fn iter(&self) -> impl Iterator<Item = Point> {
+        (0..=self.x_max).flat_map(|x| (0..=self.y_max).map(move |y| Point { x, y }))
+    }
+    /// All the positions, with whether they hold a roll.
+    pub fn iter_values(&self) -> impl Iterator<Item = (Point, bool)> {
+        self.iter().map(|p| (p, self.has_roll(&p)))
+    }
+    /// The positions around `p` which are in the warehouse, with whether they hold a roll. `p`
+    /// itself may be outside.
+    pub fn neighbors(&self, &Point { x, y }: &Point) -> impl Iterator<Item = (Point, bool)> {
+        let (x0, x1) = (x.saturating_sub(1), x.saturating_add(1).min(self.x_max));
+        let (y0, y1) = (y.saturating_sub(1), y.saturating_add(1).min(self.y_max));
+        (x0..=x1).flat_map(move |n_x| {
+            (y0..=y1).filter_map(move |n_y| {
+                if n_x != x || n_y != y {
+                    let n = Point { x: n_x, y: n_y };
+                    Some((n, self.has_roll(&n)))
+                } else {
+                    None
+                }
+            })
+        })
+    }
+}
+
+/// A grid of positions, which may hold a paper roll.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Warehouse {
+    x_max: usize,
+    y_max: usize,
+    /// Whether there is a roll, by row then column
+    paper_rolls: Vec<Vec<bool>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::example_tests! {
+        Day4,
+        ex1: { part_1: 13, part_2: 43 },
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("..@\n.#@\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 2, "#")
+        );
+        let error = parse_input("..@\n.@\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 1, ".@")
+        );
+        assert!(parse_input("").is_err());
+    }
+
+    #[test]
+    fn test_outside() {
+        let warehouse = parse_input("@@\n.@\n").unwrap();
+        assert_eq!(warehouse.get(&Point { x: 1, y: 0 }), Some(true));
+        assert_eq!(warehouse.get(&Point { x: 2, y: 0 }), None);
+        assert_eq!(
+            warehouse.neighbors(&Point { x: 2, y: 1 }).collect_vec(),
+            [(Point { x: 1, y: 0 }, true), (Point { x: 1, y: 1 }, true)]
+        );
+        let far = Point { x: usize::MAX, y: usize::MAX };
+        assert_eq!(warehouse.get(&far), None);
+        assert_eq!(warehouse.neighbors(&far).count(), 0);
+    }
+
+    #[test]
+    fn test_new() {
+        let error = Warehouse::new(vec![vec![true, false], vec![true]]).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 1, "@")
+        );
+        assert!(Warehouse::new(vec![vec![]]).is_err());
+        assert!(Warehouse::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day4>(20);
+    }
+}
@@ -0,0 +1,300 @@
+//! Day 5: Cafeteria, checking ingredient IDs against ranges of fresh IDs.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Alternative, Line, ParseError, Solution, lines};
+use std::error::Error;
+
+/// The [Solution] of day 5, on the inventory database.
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Cafeteria";
+    const INPUT_SIZE: usize = 190;
+
+    type Input = Inventory;
+    type Output1 = usize;
+    type Output2 = u64;
+
+    const ALTERNATIVES_2: &'static [Alternative<Inventory, u64>] = &[Alternative {
+        name: "sorted merge",
+        run: |inventory| part_2_sorted_merge(&inventory.fresh_ranges),
+    }];
+
+    fn parse(input: &str) -> Result<Inventory, ParseError> {
+        parse_input(input)
+    }
+
+    fn validate(input: &str) -> Vec<ParseError> {
+        let mut lines = lines(input);
+        let mut errors = lines
+            .by_ref()
+            .take_while(|line| !line.text.is_empty())
+            .filter_map(|line| parse_range(&line).err())
+            .collect_vec();
+        let ingredients = lines.map(|line| parse_ingredient(&line)).collect_vec();
+        if ingredients.is_empty() {
+            errors.push(ParseError::at_end(
+                input,
+                "a blank line, then the available ingredient IDs",
+            ));
+        }
+        errors.extend(ingredients.into_iter().filter_map(Result::err));
+        errors
+    }
+
+    fn part_1(inventory: &Inventory) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(&inventory.fresh_ranges, &inventory.ingredients))
+    }
+
+    fn part_2(inventory: &Inventory) -> Result<u64, Box<dyn Error>> {
+        part_2(&inventory.fresh_ranges)
+    }
+
+    /// `size` fresh ID ranges, and 5 times as many available ingredients.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+/// Number of available ingredients which are fresh.
+pub fn part_1(fresh_ranges: &[Range], ingredients: &[u64]) -> usize {
+    ingredients
+        .iter()
+        .filter(|ingredient| {
+            fresh_ranges
+                .iter()
+                .any(|range| range.contains(**ingredient))
+        })
+        .count()
+}
+
+/// Number of IDs which the ranges consider fresh, merging the ranges which overlap.
+pub fn part_2(fresh_ranges: &[Range]) -> Result<u64, Box<dyn Error>> {
+    let mut ranges = fresh_ranges.iter().cloned().collect_vec();
+    loop {
+        let mut merged_ranges: Vec<Range> = Vec::new();
+        for r1 in ranges.iter() {
+            match merged_ranges
+                .iter_mut()
+                .position(|r2| !(r1.end < r2.start || r1.start > r2.end))
+            {
+                Some(pos) => {
+                    let r2 = merged_ranges[pos];
+                    merged_ranges[pos] =
+                        Range { start: r1.start.min(r2.start), end: r1.end.max(r2.end) }
+                }
+                None => {
+                    merged_ranges.push(*r1);
+                }
+            }
+        }
+        if merged_ranges.len() == ranges.len() {
+            ranges = merged_ranges;
+            break;
+        } else {
+            ranges = merged_ranges;
+        }
+    }
+
+    count_ids(&ranges)
+}
+
+/// Merges the ranges in a single pass, by sorting them first.
+pub fn part_2_sorted_merge(fresh_ranges: &[Range]) -> Result<u64, Box<dyn Error>> {
+    let mut merged_ranges: Vec<Range> = Vec::new();
+    for range in fresh_ranges.iter().sorted_by_key(|r| r.start) {
+        match merged_ranges.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged_ranges.push(*range),
+        }
+    }
+    count_ids(&merged_ranges)
+}
+
+/// Number of IDs in the disjoint `ranges`, which only overflows if they cover all of them.
+fn count_ids(ranges: &[Range]) -> Result<u64, Box<dyn Error>> {
+    ranges
+        .iter()
+        .try_fold(0u64, |count, r| count.checked_add(r.len()?))
+        .ok_or_else(|| "The number of fresh IDs overflows".into())
+}
+
+/// Parses the fresh ID ranges like `3-5`, then a blank line, then an available ID per line.
+pub fn parse_input(input: &str) -> Result<Inventory, ParseError> {
+    let mut lines = lines(input);
+    let fresh_ranges = lines
+        .by_ref()
+        .take_while(|line| !line.text.is_empty())
+        .map(|line| parse_range(&line))
+        .collect::<Result<Vec<Range>, _>>()?;
+    let ingredients = lines
+        .map(|line| parse_ingredient(&line))
+        .collect::<Result<Vec<u64>, _>>()?;
+    Inventory::new(fresh_ranges, ingredients)
+        .map_err(|e| ParseError::at_end(input, format!("a blank line, then {}", e.expected)))
+}
+
+fn parse_range(line: &Line) -> Result<Range, ParseError> {
+    let (start, end) = line
+        .text
+        .split_once("-")
+        .ok_or_else(|| line.error("a range like `3-5`"))?;
+    let start = line.parse_at(start, "an ingredient ID")?;
+    let end = line.parse_at(end, "an ingredient ID")?;
+    Range::new(start, end).map_err(|e| line.error(e.expected))
+}
+
+fn parse_ingredient(line: &Line) -> Result<u64, ParseError> {
+    line.parse_at(line.text, "an ingredient ID")
+}
+
+/// Length bound of the generated ranges.
+const MAX_LEN: u64 = 1_000_000_000_000;
+
+/// Bound of the generated IDs: dense enough for the ranges to overlap now and then, and low
+/// enough for the ends of the ranges not to overflow.
+fn id_span(size: usize) -> u64 {
+    (size.max(1) as u64)
+        .saturating_mul(2 * MAX_LEN)
+        .min(u64::MAX - MAX_LEN)
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    let span = id_span(size);
+    let mut input = String::new();
+    for _ in 0..size {
+        let start = rng.random_range(1..span);
+        let end = start + rng.random_range(0..MAX_LEN);
+        input.push_str(&format!("{}-{}\n", start, end));
+    }
+    input.push('\n');
+    for _ in 0..size.max(1) * 5 {
+        input.push_str(&format!("{}\n", rng.random_range(1..span)));
+    }
+    input
+}
+
+/// The fresh ID ranges and the IDs of the available ingredients.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Inventory {
+    fresh_ranges: Vec<Range>,
+    ingredients: Vec<u64>,
+}
+
+impl Inventory {
+    /// The inventory of these ranges and ingredients, of which there must be at least one.
+    pub fn new(fresh_ranges: Vec<Range>, ingredients: Vec<u64>) -> Result<Inventory, ParseError> {
+        if ingredients.is_empty() {
+            return Err(ParseError::in_value("", "the available ingredient IDs"));
+        }
+        Ok(Inventory { fresh_ranges, ingredients })
+    }
+    pub fn fresh_ranges(&self) -> &[Range] {
+        &self.fresh_ranges
+    }
+    pub fn ingredients(&self) -> &[u64] {
+        &self.ingredients
+    }
+}
+
+impl Range {
+    /// The range from `start` to `end`, which must not be reversed since part 2 computes the
+    /// lengths of the ranges.
+    pub fn new(start: u64, end: u64) -> Result<Range, ParseError> {
+        if start > end {
+            let text = format!("{}-{}", start, end);
+            return Err(ParseError::in_value(&text, "a range with start <= end"));
+        }
+        Ok(Range { start, end })
+    }
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+    pub fn contains(&self, x: u64) -> bool {
+        self.start <= x && x <= self.end
+    }
+    fn len(&self) -> Option<u64> {
+        (self.end - self.start).checked_add(1)
+    }
+}
+
+/// A range of ingredient IDs, bounds included.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Range {
+    start: u64,
+    end: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::proptest::collection::vec;
+    use common::proptest::prelude::*;
+
+    common::example_tests! {
+        Day5,
+        ex1: { part_1: 3, part_2: 14 },
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day5::validate("3-5\n14-10\n\n1\nx\n");
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.line, e.snippet.as_str()))
+                .collect_vec(),
+            [(2, "14-10"), (5, "x")]
+        );
+        let error = parse_input("3-5\n").unwrap_err();
+        assert_eq!(
+            error.expected,
+            "a blank line, then the available ingredient IDs"
+        );
+        assert!(Inventory::new(Vec::new(), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_all_ids() {
+        let ranges = [Range { start: 0, end: u64::MAX }];
+        assert!(part_2(&ranges).is_err());
+        assert!(part_2_sorted_merge(&ranges).is_err());
+        assert!(Range::new(u64::MAX, 0).is_err());
+        let ranges = [Range { start: 1, end: u64::MAX }];
+        assert_eq!(part_2(&ranges).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let ranges = || {
+            vec((0u64..40, 0u64..10), 0..8).prop_map(|ranges| {
+                ranges
+                    .into_iter()
+                    .map(|(start, len)| Range { start, end: start + len })
+                    .collect_vec()
+            })
+        };
+        let brute_force = |ranges: &Vec<Range>| {
+            (0..50)
+                .filter(|id| ranges.iter().any(|r| r.contains(*id)))
+                .count() as u64
+        };
+        common::check_against_reference(ranges(), brute_force, |ranges| part_2(ranges).unwrap());
+        common::check_against_reference(ranges(), brute_force, |ranges| {
+            part_2_sorted_merge(ranges).unwrap()
+        });
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day5>(20);
+        assert_eq!(id_span(20), 40_000_000_000_000);
+        assert_eq!(id_span(usize::MAX), u64::MAX - MAX_LEN);
+    }
+}
@@ -0,0 +1,352 @@
+//! Day 6: Trash Compactor, solving a worksheet of math problems laid out in columns.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Line, ParseError, Solution, lines};
+use std::error::Error;
+
+/// The [Solution] of day 6, on a worksheet.
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Trash Compactor";
+    const INPUT_SIZE: usize = 1000;
+
+    type Input = Worksheet;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Worksheet, ParseError> {
+        parse_input(input)
+    }
+
+    /// Part 2 reads the worksheet column by column, from right to left: all the lines must be
+    /// as long as the operators line, and each problem starts with its operator, after a blank
+    /// column.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let all_lines = lines(input).collect_vec();
+        let Some((ops_line, number_lines)) = all_lines.split_last() else {
+            return vec![ParseError::at_end(input, "a worksheet")];
+        };
+        let width = ops_line.text.len();
+        let mut errors = Vec::new();
+
+        let ops_chars = ops_line.text.char_indices().collect_vec();
+        for &(i, c) in &ops_chars {
+            if !matches!(c, '+' | '*' | ' ') {
+                errors.push(ops_line.error_at_char(i, "an operator, `+` or `*`"));
+            }
+        }
+        if ops_chars.first().is_none_or(|&(_, c)| c == ' ') {
+            errors.push(ops_line.error_at_char(0, "an operator in the first column"));
+        }
+        for line in number_lines {
+            for (i, c) in line.text.char_indices() {
+                if !c.is_ascii_digit() && c != ' ' {
+                    errors.push(line.error_at_char(i, "a digit"));
+                }
+            }
+            if line.text.len() != width {
+                errors.push(line.error(format!(
+                    "a line of {} characters, as long as the operators line",
+                    width
+                )));
+            }
+        }
+        // Other lengths and characters are already reported
+        let well_formed_lines = all_lines
+            .iter()
+            .filter(|line| line.text.len() == width && line.text.is_ascii())
+            .collect_vec();
+        for &(x, c) in &ops_chars {
+            if x == 0 || !matches!(c, '+' | '*') {
+                continue;
+            }
+            for line in &well_formed_lines {
+                if line.text.as_bytes()[x - 1] != b' ' {
+                    errors.push(line.error_at_char(x - 1, "a blank column before each operator"));
+                }
+            }
+        }
+        errors
+    }
+
+    fn part_1(worksheet: &Worksheet) -> Result<u64, Box<dyn Error>> {
+        part_1(worksheet)
+    }
+
+    fn part_2(worksheet: &Worksheet) -> Result<u64, Box<dyn Error>> {
+        part_2(worksheet)
+    }
+
+    /// A worksheet of `size` problems.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+/// Grand total of the problems, with numbers written in rows.
+pub fn part_1(worksheet: &Worksheet) -> Result<u64, Box<dyn Error>> {
+    let ops = worksheet
+        .ops_line
+        .split_whitespace()
+        .map(|x| {
+            if x == "*" {
+                Ok(Operator::Mult)
+            } else if x == "+" {
+                Ok(Operator::Add)
+            } else {
+                Err(format!("{:?} is not an operator", x))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut operations = ops
+        .iter()
+        .map(|&op| Operation { numbers: Vec::new(), op })
+        .collect_vec();
+    for line in worksheet.number_lines.iter() {
+        for (idx, entry) in line.split_whitespace().enumerate() {
+            operations
+                .get_mut(idx)
+                .ok_or(format!("Missing operation at idx {}", idx))?
+                .numbers
+                .push(entry.parse()?);
+        }
+    }
+
+    compute_operations(&operations)
+}
+
+/// Grand total of the problems, with numbers written in columns, right to left.
+pub fn part_2(worksheet: &Worksheet) -> Result<u64, Box<dyn Error>> {
+    // Parse each line separately (character by character)
+    let ops_line = worksheet
+        .ops_line
+        .chars()
+        .map(|c| match c {
+            '+' => Some(Operator::Add),
+            '*' => Some(Operator::Mult),
+            _ => None,
+        })
+        .collect_vec();
+    let number_lines = worksheet
+        .number_lines
+        .iter()
+        .map(|l| {
+            l.chars()
+                .map(|c| c.to_digit(10).map(|d| d as u64))
+                .collect_vec()
+        })
+        .collect_vec();
+    // Unlike part 1, the columns must line up (see `validate`)
+    if number_lines.iter().any(|line| line.len() != ops_line.len()) {
+        return Err("The lines must be as long as the operators line".into());
+    }
+
+    // Move form right to left
+    let mut operations = Vec::new();
+    // The worksheet has at least one operator
+    let mut x = ops_line.len() - 1;
+    let mut numbers = Vec::new();
+    loop {
+        let number = number_lines
+            .iter()
+            .try_fold(0u64, |acc, line| match line[x] {
+                Some(d) => acc.checked_mul(10)?.checked_add(d),
+                None => Some(acc),
+            })
+            .ok_or(OVERFLOW)?;
+        numbers.push(number);
+        match ops_line[x] {
+            None => {
+                x = x
+                    .checked_sub(1)
+                    .ok_or("The first column must have an operator")?;
+            }
+            Some(op) => {
+                operations.push(Operation { numbers, op });
+                numbers = Vec::new();
+                if x == 0 {
+                    break;
+                } else {
+                    x = x
+                        .checked_sub(2)
+                        .ok_or("There must be a blank column before each operator")?;
+                }
+            }
+        }
+    }
+
+    compute_operations(&operations)
+}
+
+/// Splits the lines of numbers from the last line, of operators, which [Worksheet::new] checks.
+pub fn parse_input(input: &str) -> Result<Worksheet, ParseError> {
+    let mut lines = input.lines().map(str::to_owned).collect_vec();
+    let ops_line = lines
+        .pop()
+        .ok_or_else(|| ParseError::at_end(input, "a worksheet"))?;
+    Worksheet::new(lines, ops_line)
+}
+
+const OVERFLOW: &str = "The grand total overflows";
+
+fn compute_operations(operations: &[Operation]) -> Result<u64, Box<dyn Error>> {
+    operations
+        .iter()
+        .try_fold(0u64, |total, Operation { op, numbers }| {
+            let result = match op {
+                Operator::Mult => numbers.iter().try_fold(1u64, |a, &b| a.checked_mul(b)),
+                Operator::Add => numbers.iter().try_fold(0u64, |a, &b| a.checked_add(b)),
+            };
+            total.checked_add(result?)
+        })
+        .ok_or_else(|| OVERFLOW.into())
+}
+
+/// Numbers of up to 3 digits, so that the grand total fits in a `u64` even for a million
+/// problems.
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    const ROWS: usize = 4;
+    let mut lines = vec![String::new(); ROWS + 1];
+    for problem in 0..size.max(1) {
+        if problem > 0 {
+            lines.iter_mut().for_each(|line| line.push(' '));
+        }
+        let width = rng.random_range(1..=3);
+        let align_left = rng.random_bool(0.5);
+        for line in &mut lines[..ROWS] {
+            let number = (0..rng.random_range(1..=width))
+                .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                .collect::<String>();
+            if align_left {
+                line.push_str(&format!("{:<width$}", number, width = width));
+            } else {
+                line.push_str(&format!("{:>width$}", number, width = width));
+            }
+        }
+        let op = if rng.random_bool(0.5) { '+' } else { '*' };
+        lines[ROWS].push_str(&format!("{:<width$}", op, width = width));
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// The raw lines of the worksheet, since their alignment matters for part 2.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Worksheet {
+    number_lines: Vec<String>,
+    ops_line: String,
+}
+
+impl Worksheet {
+    /// The worksheet with these lines of numbers, then the line of operators. Only checks the
+    /// characters and the number of columns, since each part reads the numbers differently. The
+    /// errors point at the lines as they would be written in an input.
+    pub fn new(number_lines: Vec<String>, ops_line: String) -> Result<Worksheet, ParseError> {
+        let ops = Line { number: number_lines.len() + 1, text: &ops_line };
+        if let Some((i, _)) = ops
+            .text
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '+' | '*' | ' '))
+        {
+            return Err(ops.error_at_char(i, "an operator, `+` or `*`"));
+        }
+        let op_count = ops.text.split_whitespace().count();
+        if op_count == 0 {
+            return Err(ops.error("a line of operators"));
+        }
+
+        for (i, text) in number_lines.iter().enumerate() {
+            let line = Line { number: i + 1, text };
+            if let Some((i, _)) = line
+                .text
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_digit() && *c != ' ')
+            {
+                return Err(line.error_at_char(i, "a digit"));
+            }
+            let numbers = line.text.split_whitespace().collect_vec();
+            let expected = format!("{} numbers, one per operator", op_count);
+            if numbers.len() > op_count {
+                return Err(line.error_at(numbers[op_count], expected));
+            } else if numbers.len() < op_count {
+                return Err(line.error_at_char(line.text.len(), expected));
+            }
+        }
+
+        Ok(Worksheet { number_lines, ops_line })
+    }
+    pub fn number_lines(&self) -> &[String] {
+        &self.number_lines
+    }
+    pub fn ops_line(&self) -> &str {
+        &self.ops_line
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Operation {
+    numbers: Vec<u64>,
+    op: Operator,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Operator {
+    Mult,
+    Add,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::example_tests! {
+        Day6,
+        ex1: { part_1: 4277556, part_2: 3263827 },
+    }
+
+    #[test]
+    fn test_overflow() {
+        let worksheet = parse_input("4294967296\n4294967296\n*         \n").unwrap();
+        assert!(part_1(&worksheet).is_err());
+        let worksheet = parse_input(&format!("{}+\n", "9\n".repeat(20))).unwrap();
+        assert!(part_2(&worksheet).is_err());
+    }
+
+    #[test]
+    fn test_misaligned() {
+        for input in ["1 2 \n+ *\n", "111\n  +\n", "11\n+*\n"] {
+            assert!(part_2(&parse_input(input).unwrap()).is_err(), "{:?}", input);
+        }
+        let error = Worksheet::new(vec!["1 2".to_string()], "+".to_string()).unwrap_err();
+        assert_eq!((error.line, error.snippet.as_str()), (1, "2"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day6::validate("12 34\n1x 3\n + * ");
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.line, e.column, e.expected.as_str()))
+                .collect_vec(),
+            [
+                (3, 1, "an operator in the first column"),
+                (2, 2, "a digit"),
+                (
+                    2,
+                    1,
+                    "a line of 5 characters, as long as the operators line"
+                ),
+                (1, 1, "a blank column before each operator"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day6>(20);
+    }
+}
@@ -0,0 +1,342 @@
+//! Day 7: Laboratories, following a tachyon beam through a manifold of splitters.
+
+use common::itertools::Itertools;
+use common::maplit::{hashmap, hashset};
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Line, ParseError, Query, Solution, lines};
+use std::collections::HashMap;
+use std::error::Error;
+
+/// The [Solution] of day 7, on a manifold diagram.
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Laboratories";
+    const INPUT_SIZE: usize = 70;
+
+    type Input = Manifold;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Manifold, ParseError> {
+        parse_input(input)
+    }
+
+    /// The manifold must be a rectangle with a single start position, and without splitters on
+    /// the edges, which would split the beam out of the manifold.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let mut lines = lines(input);
+        let Some(first_line) = lines.next() else {
+            return vec![ParseError::at_end(
+                input,
+                "a first line with the start position `S`",
+            )];
+        };
+        let width = first_line.text.len();
+        let mut errors = char_errors(
+            &first_line,
+            &['.', 'S'],
+            "an empty space `.` or the start position `S`",
+        );
+        match first_line
+            .text
+            .match_indices('S')
+            .map(|(i, _)| i)
+            .collect_vec()[..]
+        {
+            [] => errors.push(first_line.error("a first line with the start position `S`")),
+            [_] => {}
+            [_, second, ..] => errors.push(first_line.error_at_char(second, "a single `S`")),
+        }
+        for line in lines {
+            errors.extend(char_errors(
+                &line,
+                &['.', '^'],
+                "an empty space `.` or a splitter `^`",
+            ));
+            if line.text.len() != width {
+                errors.push(line.error(format!("a row of {} cells", width)));
+            } else if line.text.starts_with('^') {
+                errors.push(line.error_at_char(0, "no splitter on the edge"));
+            } else if line.text.ends_with('^') {
+                errors.push(line.error_at_char(width - 1, "no splitter on the edge"));
+            }
+        }
+        errors
+    }
+
+    fn part_1(manifold: &Manifold) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(manifold))
+    }
+
+    fn part_2(manifold: &Manifold) -> Result<usize, Box<dyn Error>> {
+        part_2(manifold)
+    }
+
+    /// A manifold with `size` rows of splitters.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(manifold: &Manifold) -> Vec<(&'static str, String)> {
+        let splitters = manifold
+            .splitters
+            .iter()
+            .map(|row| row.len())
+            .sum::<usize>();
+        vec![
+            ("rows", (manifold.splitters.len() + 1).to_string()),
+            ("start column", manifold.start_position.to_string()),
+            ("splitters", splitters.to_string()),
+        ]
+    }
+
+    const QUERIES: &'static [Query] = &[
+        Query {
+            name: "row",
+            args: "<n>",
+            help: "columns of the splitters of a row (from 1, the start row)",
+        },
+        Query {
+            name: "beams",
+            args: "<n>",
+            help: "columns of the beams below a row, with their number of timelines",
+        },
+    ];
+
+    fn query(manifold: &Manifold, name: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+        let [row] = args else {
+            return Err(format!("Usage: {} <n>", name).into());
+        };
+        let row = row.parse::<usize>()?;
+        if row == 0 || row > manifold.splitters.len() + 1 {
+            return Err(format!("No row {}", row).into());
+        }
+        match name {
+            "row" if row == 1 => Ok(format!("start at {}", manifold.start_position)),
+            "row" => Ok(manifold.splitters[row - 2].iter().join(" ")),
+            _ => Ok(timelines(manifold, row - 1)?
+                .into_iter()
+                .sorted()
+                .map(|(position, count)| format!("{}: {}", position, count))
+                .join("\n")),
+        }
+    }
+}
+
+/// Number of times the beam is split.
+pub fn part_1(manifold: &Manifold) -> usize {
+    let mut split_count = 0;
+    let mut streams = hashset![manifold.start_position];
+    for splitters_line in manifold.splitters.iter() {
+        streams = streams
+            .iter()
+            .flat_map(|position| {
+                if splitters_line.contains(position) {
+                    split_count += 1;
+                    split(*position)
+                } else {
+                    vec![*position]
+                }
+            })
+            .collect()
+    }
+    split_count
+}
+
+/// Number of timelines of a single particle, which takes both ways at each splitter.
+pub fn part_2(manifold: &Manifold) -> Result<usize, Box<dyn Error>> {
+    timelines(manifold, manifold.splitters.len())?
+        .values()
+        .try_fold(0usize, |sum, count| sum.checked_add(*count))
+        .ok_or_else(|| OVERFLOW.into())
+}
+
+const OVERFLOW: &str = "The number of timelines overflows";
+
+/// Number of timelines of each beam position, after the first `rows` rows of splitters.
+pub fn timelines(
+    manifold: &Manifold,
+    rows: usize,
+) -> Result<HashMap<usize, usize>, Box<dyn Error>> {
+    let mut streams = hashmap! { manifold.start_position => 1usize };
+    for splitters_line in manifold.splitters.iter().take(rows) {
+        let mut new_streams = HashMap::new();
+        for (position, count) in streams.into_iter() {
+            let positions = if splitters_line.contains(&position) {
+                split(position)
+            } else {
+                vec![position]
+            };
+            for position in positions {
+                let c = new_streams.entry(position).or_insert(0usize);
+                *c = c.checked_add(count).ok_or(OVERFLOW)?;
+            }
+        }
+        streams = new_streams
+    }
+    Ok(streams)
+}
+
+/// The positions of the two beams split at `position`. A splitter on the left edge, which
+/// validation rejects, would split a beam out of the manifold.
+fn split(position: usize) -> Vec<usize> {
+    position
+        .checked_sub(1)
+        .into_iter()
+        .chain([position + 1])
+        .collect()
+}
+
+/// Parses a first line with the start `S`, then rows of `.` and splitters `^`.
+pub fn parse_input(input: &str) -> Result<Manifold, ParseError> {
+    let mut lines = lines(input);
+    let first_line = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "a first line with the start position `S`"))?;
+    check_chars(
+        &first_line,
+        &['.', 'S'],
+        "an empty space `.` or the start position `S`",
+    )?;
+    let start_x = first_line
+        .text
+        .chars()
+        .position(|c| c == 'S')
+        .ok_or_else(|| first_line.error("a first line with the start position `S`"))?;
+    let splitters = lines
+        .map(|line| {
+            check_chars(&line, &['.', '^'], "an empty space `.` or a splitter `^`")?;
+            Ok(line.text.chars().positions(|c| c == '^').collect_vec())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Manifold::new(start_x, splitters)
+}
+
+fn char_errors(line: &Line, allowed: &[char], expected: &str) -> Vec<ParseError> {
+    line.text
+        .char_indices()
+        .filter(|(_, c)| !allowed.contains(c))
+        .map(|(i, _)| line.error_at_char(i, expected))
+        .collect()
+}
+
+fn check_chars(line: &Line, allowed: &[char], expected: &str) -> Result<(), ParseError> {
+    match line.text.char_indices().find(|(_, c)| !allowed.contains(c)) {
+        Some((i, _)) => Err(line.error_at_char(i, expected)),
+        None => Ok(()),
+    }
+}
+
+/// The splitters are within reach of the beam, and get sparser as the manifold grows, so that the
+/// number of timelines fits in a `usize`.
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    let start = size + 1;
+    let width = 2 * start + 1;
+    let density = (35.0 / size.max(1) as f64).min(0.5);
+    let mut first_row = ".".repeat(width);
+    first_row.replace_range(start..=start, "S");
+    let mut rows = vec![first_row];
+    for k in 0..size {
+        rows.push(".".repeat(width));
+        let row = (0..width)
+            .map(|x| {
+                let reachable = x.abs_diff(start) <= k && (x + k - start).is_multiple_of(2);
+                if reachable && rng.random_bool(density) {
+                    '^'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        rows.push(row);
+    }
+    rows.iter().map(|row| format!("{}\n", row)).collect()
+}
+
+/// Where the beam starts, and the positions of the splitters of each row below.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Manifold {
+    start_position: usize,
+    splitters: Vec<Vec<usize>>,
+}
+
+impl Manifold {
+    /// The manifold with the beam starting at `start_position`, and the splitters of each row
+    /// below, which must be left of `usize::MAX` so that they can split a beam to the right. The
+    /// errors point at the rows of splitter positions, numbered as in an input.
+    pub fn new(start_position: usize, splitters: Vec<Vec<usize>>) -> Result<Manifold, ParseError> {
+        if let Some((i, row)) = splitters
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.contains(&usize::MAX))
+        {
+            let text = row.iter().join(",");
+            let line = Line { number: i + 2, text: &text };
+            return Err(line.error(format!("splitter positions below {}", usize::MAX)));
+        }
+        Ok(Manifold { start_position, splitters })
+    }
+    pub fn start_position(&self) -> usize {
+        self.start_position
+    }
+    /// The positions of the splitters of each row, from the row below the start
+    pub fn splitters(&self) -> &[Vec<usize>] {
+        &self.splitters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::example_tests! {
+        Day7,
+        ex1: { part_1: 21, part_2: 40 },
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day7::validate("..S.S\n^....\n..x.\n");
+        assert_eq!(
+            errors.iter().map(|e| (e.line, e.column)).collect_vec(),
+            [(1, 5), (2, 1), (3, 3), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_edges() {
+        let manifold = parse_input("S.\n^.\n").unwrap();
+        assert_eq!(part_1(&manifold), 1);
+        assert_eq!(part_2(&manifold).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Manifold::new(usize::MAX, vec![vec![1]]).is_ok());
+        let error = Manifold::new(3, vec![vec![2], vec![1, usize::MAX]]).unwrap_err();
+        assert_eq!(
+            (error.line, error.snippet),
+            (3, format!("1,{}", usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let row = format!(".{}.\n", "^".repeat(199));
+        let input = format!(
+            "{}S{}\n{}",
+            ".".repeat(100),
+            ".".repeat(100),
+            row.repeat(70)
+        );
+        assert!(part_2(&parse_input(&input).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day7>(20);
+    }
+}
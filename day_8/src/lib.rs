@@ -0,0 +1,212 @@
+//! Day 8: Playground, connecting junction boxes into circuits, closest pairs first.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::tracing;
+use common::{Line, Param, ParseError, Solution, lines, parse_each_line};
+use std::error::Error;
+
+/// The [Solution] of day 8, on a list of junction boxes.
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Playground";
+    const INPUT_SIZE: usize = 1000;
+
+    type Input = Vec<JunctionBox>;
+    type Output1 = usize;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<JunctionBox>, ParseError> {
+        parse_input(input)
+    }
+
+    fn validate(input: &str) -> Vec<ParseError> {
+        parse_each_line(input, parse_junction_box).1
+    }
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "connections",
+        default: "1000",
+        help: "number of closest pairs to connect in part 1",
+    }];
+
+    fn part_1(junction_boxes: &Vec<JunctionBox>) -> Result<usize, Box<dyn Error>> {
+        Ok(part_1(junction_boxes, Self::param("connections")?))
+    }
+
+    fn part_2(junction_boxes: &Vec<JunctionBox>) -> Result<i64, Box<dyn Error>> {
+        Ok(part_2(junction_boxes))
+    }
+
+    /// `size` junction boxes.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+}
+
+/// Product of the sizes of the 3 largest circuits, after connecting the `max_connections`
+/// closest pairs.
+pub fn part_1(junction_boxes: &[JunctionBox], max_connections: usize) -> usize {
+    let closest_pairs = closest_pairs(junction_boxes).take(max_connections);
+
+    let mut circuits = junction_boxes.iter().map(|j| vec![j]).collect_vec();
+    for (p1, p2) in closest_pairs {
+        let pos1 = circuits.iter().position(|c| c.contains(&&p1)).unwrap();
+        let pos2 = circuits.iter().position(|c| c.contains(&&p2)).unwrap();
+        if pos1 != pos2 {
+            let c1_pos = pos1.min(pos2);
+            let c2_pos = pos1.max(pos2);
+            let c2 = circuits.remove(c2_pos);
+            circuits[c1_pos].extend(c2.iter());
+            tracing::debug!(
+                size = circuits[c1_pos].len(),
+                circuits = circuits.len(),
+                "merged two circuits"
+            );
+        }
+    }
+
+    circuits
+        .iter()
+        .map(|circuit| circuit.len())
+        .sorted()
+        .rev()
+        .take(3)
+        .product()
+}
+
+/// Product of the X coordinates of the last pair connected to get a single circuit.
+pub fn part_2(junction_boxes: &[JunctionBox]) -> i64 {
+    let mut circuits = junction_boxes.iter().map(|j| vec![j]).collect_vec();
+    for (p1, p2) in closest_pairs(junction_boxes) {
+        let pos1 = circuits.iter().position(|c| c.contains(&&p1)).unwrap();
+        let pos2 = circuits.iter().position(|c| c.contains(&&p2)).unwrap();
+        if pos1 != pos2 {
+            let c1_pos = pos1.min(pos2);
+            let c2_pos = pos1.max(pos2);
+            let c2 = circuits.remove(c2_pos);
+            circuits[c1_pos].extend(c2.iter());
+            tracing::debug!(
+                size = circuits[c1_pos].len(),
+                circuits = circuits.len(),
+                "merged two circuits"
+            );
+        }
+        if circuits.len() == 1 {
+            return p1.x * p2.x;
+        }
+    }
+    0
+}
+
+/// All the pairs of junction boxes, closest first.
+pub fn closest_pairs(
+    junction_boxes: &[JunctionBox],
+) -> impl Iterator<Item = (JunctionBox, JunctionBox)> {
+    junction_boxes
+        .iter()
+        .tuple_combinations::<(_, _)>()
+        .map(|(p1, p2)| (p1.distance_to_squared(p2), p1, p2))
+        .sorted_by_key(|(dist, _, _)| *dist)
+        .map(|(_, id1, id2)| (*id1, *id2))
+}
+
+/// Parses a junction box per line, like `162,817,812`.
+pub fn parse_input(input: &str) -> Result<Vec<JunctionBox>, ParseError> {
+    lines(input).map(|line| parse_junction_box(&line)).collect()
+}
+
+/// Bound of the coordinates, so that the squared distances fit in an `i64`.
+pub const MAX_COORDINATE: i64 = 500_000_000;
+
+fn parse_junction_box(line: &Line) -> Result<JunctionBox, ParseError> {
+    let coordinates: Vec<i64> = line.parse_separated(line.text, ',', "a coordinate")?;
+    match coordinates[..] {
+        [x, y, z] => JunctionBox::new(x, y, z).map_err(|e| line.error(e.expected)),
+        _ => Err(line.error("3 coordinates like `162,817,812`")),
+    }
+}
+
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.random_range(0..100_000));
+            format!("{},{},{}\n", x, y, z)
+        })
+        .collect()
+}
+
+/// The position of a junction box.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct JunctionBox {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl JunctionBox {
+    /// The junction box at `(x, y, z)`, whose coordinates must be within ±[MAX_COORDINATE].
+    pub fn new(x: i64, y: i64, z: i64) -> Result<JunctionBox, ParseError> {
+        if [x, y, z]
+            .iter()
+            .any(|c| !(-MAX_COORDINATE..=MAX_COORDINATE).contains(c))
+        {
+            let text = format!("{},{},{}", x, y, z);
+            let expected = format!("coordinates within ±{}", MAX_COORDINATE);
+            return Err(ParseError::in_value(&text, expected));
+        }
+        Ok(JunctionBox { x, y, z })
+    }
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+    pub fn y(&self) -> i64 {
+        self.y
+    }
+    pub fn z(&self) -> i64 {
+        self.z
+    }
+    pub fn distance_to_squared(&self, other: &JunctionBox) -> i64 {
+        // Actual distance is the square root of this, but for comparisons we don't care
+        (self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::AnySolution;
+
+    common::example_tests! {
+        Day8,
+        ex1: { part_2: 25272 },
+    }
+
+    #[test]
+    fn test_part_1() {
+        // The example only makes the 10 shortest connections, instead of 1000
+        let junction_boxes = Day8::parse(common::example!(ex1)).unwrap();
+        assert_eq!(part_1(&junction_boxes, 10), 40);
+
+        let connections = [("connections".to_string(), "10".to_string())];
+        Day8.set_params(&connections).unwrap();
+        assert_eq!(Day8::part_1(&junction_boxes).unwrap(), 40);
+        Day8.set_params(&[]).unwrap();
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("1,2,3\n-9223372036854775808,0,0\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(parse_input("500000000,0,-500000000\n").is_ok());
+        assert!(JunctionBox::new(0, 500_000_001, 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day8>(20);
+    }
+}
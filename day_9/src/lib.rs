@@ -0,0 +1,467 @@
+//! Day 9: Movie Theater, finding the largest rectangles with red tiles in opposite corners.
+
+use common::itertools::Itertools;
+use common::rand::Rng;
+use common::rand::rngs::StdRng;
+use common::{Line, ParseError, Solution, lines, parse_each_line};
+use std::error::Error;
+
+/// The [Solution] of day 9, on the loop of red tiles.
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Movie Theater";
+    const INPUT_SIZE: usize = 500;
+
+    type Input = Vec<Point>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+        parse_input(input)
+    }
+
+    /// The red tiles must form a loop of horizontal and vertical segments.
+    fn validate(input: &str) -> Vec<ParseError> {
+        let (tiles, mut errors) = parse_each_line(input, parse_point);
+        if tiles.is_empty() && errors.is_empty() {
+            errors.push(ParseError::at_end(input, "at least one red tile"));
+        }
+        // Skipping the invalid lines would report bogus segments
+        if errors.is_empty() {
+            let closing = tiles.last().zip(tiles.first());
+            for ((_, p1), (line, p2)) in tiles.iter().tuple_windows().chain(closing) {
+                if p1.x != p2.x && p1.y != p2.y {
+                    errors.push(line.error("a tile in the same row or column as the previous one"));
+                }
+            }
+        }
+        errors
+    }
+
+    fn part_1(tiles: &Vec<Point>) -> Result<i64, Box<dyn Error>> {
+        Ok(part_1(tiles))
+    }
+
+    fn part_2(tiles: &Vec<Point>) -> Result<i64, Box<dyn Error>> {
+        part_2(tiles)
+    }
+
+    /// A loop of about `size` red tiles.
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate_input(rng, size)
+    }
+
+    fn stats(tiles: &Vec<Point>) -> Vec<(&'static str, String)> {
+        let (x_min, x_max) = tiles
+            .iter()
+            .map(|p| p.x)
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        let (y_min, y_max) = tiles
+            .iter()
+            .map(|p| p.y)
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        vec![
+            ("red tiles", tiles.len().to_string()),
+            ("x range", format!("{}..={}", x_min, x_max)),
+            ("y range", format!("{}..={}", y_min, y_max)),
+        ]
+    }
+}
+
+/// Area of the largest rectangle with red tiles in 2 opposite corners.
+pub fn part_1(tiles: &[Point]) -> i64 {
+    tiles
+        .iter()
+        .tuple_combinations::<(_, _)>()
+        .map(|(p1, p2)| Rectangle::new(p1, p2).area())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Area of the largest such rectangle which is inside the loop of red tiles.
+pub fn part_2(tiles: &[Point]) -> Result<i64, Box<dyn Error>> {
+    let closing = tiles.last().zip(tiles.first());
+    let circumference = tiles
+        .iter()
+        .tuple_windows::<(_, _)>()
+        .chain(closing)
+        .map(|(p1, p2)| {
+            Segment::new(p1, p2).ok_or_else(|| {
+                format!(
+                    "The tiles {},{} and {},{} are not in the same row or column",
+                    p1.x, p1.y, p2.x, p2.y
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let floor = Floor::new(&circumference);
+
+    Ok(tiles
+        .iter()
+        .tuple_combinations::<(_, _)>()
+        .map(|(p1, p2)| Rectangle::new(p1, p2))
+        .sorted_by_key(|r| r.area())
+        .rev()
+        .find(|r| floor.is_red_or_green(r))
+        .map_or(0, |r| r.area()))
+}
+
+/// Parses a red tile per line, like `7,1`.
+pub fn parse_input(input: &str) -> Result<Vec<Point>, ParseError> {
+    lines(input).map(|line| parse_point(&line)).collect()
+}
+
+/// Bound of the coordinates, so that the areas fit in an `i64`.
+pub const MAX_COORDINATE: i64 = 1_000_000_000;
+
+fn parse_point(line: &Line) -> Result<Point, ParseError> {
+    let coordinates: Vec<i64> = line.parse_separated(line.text, ',', "a coordinate")?;
+    match coordinates[..] {
+        [x, y] => Point::new(x, y).map_err(|e| line.error(e.expected)),
+        _ => Err(line.error("2 coordinates like `7,1`")),
+    }
+}
+
+/// A loop of `size / 4` columns side by side, each one overlapping the next, going left to right
+/// along their tops, then back along their bottoms.
+fn generate_input(rng: &mut StdRng, size: usize) -> String {
+    const MAX_Y: i64 = 100_000;
+    let columns = (size / 4).max(1);
+    let mut xs = vec![rng.random_range(1..1000)];
+    // Bottom and top of each column
+    let bottom = rng.random_range(0..MAX_Y / 2);
+    let mut spans = vec![(bottom, rng.random_range(bottom + 1..=MAX_Y))];
+    for _ in 0..columns {
+        xs.push(xs[xs.len() - 1] + rng.random_range(1..=1000));
+    }
+    while spans.len() < columns {
+        let (prev_bottom, prev_top) = spans[spans.len() - 1];
+        let top = rng.random_range(prev_bottom + 1..=MAX_Y);
+        // Low enough for the next column to find a different top
+        let bottom = rng.random_range(0..top.min(prev_top).min(MAX_Y / 2));
+        // Equal heights would make a tile in the middle of a straight segment
+        if top != prev_top && bottom != prev_bottom {
+            spans.push((bottom, top));
+        }
+    }
+
+    let mut tiles = vec![(xs[0], spans[0].0), (xs[0], spans[0].1)];
+    for i in 1..columns {
+        tiles.extend([(xs[i], spans[i - 1].1), (xs[i], spans[i].1)]);
+    }
+    let (last_bottom, last_top) = spans[columns - 1];
+    tiles.extend([(xs[columns], last_top), (xs[columns], last_bottom)]);
+    for i in (1..columns).rev() {
+        tiles.extend([(xs[i], spans[i].0), (xs[i], spans[i - 1].0)]);
+    }
+    tiles
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+/// The position of a tile.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    /// The tile at `(x, y)`, whose coordinates must be within ±[MAX_COORDINATE].
+    pub fn new(x: i64, y: i64) -> Result<Point, ParseError> {
+        if [x, y]
+            .iter()
+            .any(|c| !(-MAX_COORDINATE..=MAX_COORDINATE).contains(c))
+        {
+            let text = format!("{},{}", x, y);
+            let expected = format!("coordinates within ±{}", MAX_COORDINATE);
+            return Err(ParseError::in_value(&text, expected));
+        }
+        Ok(Point { x, y })
+    }
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+    pub fn y(&self) -> i64 {
+        self.y
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Rectangle {
+    top_left: Point,
+    bottom_right: Point,
+}
+
+impl Rectangle {
+    fn new(p1: &Point, p2: &Point) -> Rectangle {
+        Rectangle {
+            top_left: Point { x: p1.x.min(p2.x), y: p1.y.max(p2.y) },
+            bottom_right: Point { x: p1.x.max(p2.x), y: p1.y.min(p2.y) },
+        }
+    }
+    fn area(&self) -> i64 {
+        (self.bottom_right.x - self.top_left.x + 1) * (self.top_left.y - self.bottom_right.y + 1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Segment {
+    Vert { x: i64, y_min: i64, y_max: i64 },
+    Horiz { x_min: i64, x_max: i64, y: i64 },
+}
+
+impl Segment {
+    /// The segment between two tiles, unless it is diagonal.
+    fn new(p1: &Point, p2: &Point) -> Option<Segment> {
+        if p1.x == p2.x {
+            Some(Segment::Vert { x: p1.x, y_min: p1.y.min(p2.y), y_max: p1.y.max(p2.y) })
+        } else if p1.y == p2.y {
+            Some(Segment::Horiz { x_min: p1.x.min(p2.x), x_max: p1.x.max(p2.x), y: p1.y })
+        } else {
+            None
+        }
+    }
+}
+
+/// Which tiles are red or green (on the loop or inside it), on a grid compressed to the rows and
+/// columns of the red tiles, and a row or column between each two of them: the tiles in between
+/// are all alike.
+struct Floor {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// Number of tiles of the grid which are neither red nor green, in the rectangle between
+    /// `(0, 0)` and each `(i, j)` (excluded), to count them in any rectangle
+    outside: Vec<Vec<usize>>,
+}
+
+impl Floor {
+    fn new(circumference: &[Segment]) -> Floor {
+        let compress = |values: Vec<i64>| {
+            let values = values.into_iter().sorted().dedup().collect_vec();
+            let between = values
+                .iter()
+                .tuple_windows()
+                .filter(|(a, b)| *b - *a > 1)
+                .map(|(a, _)| a + 1)
+                .collect_vec();
+            values.into_iter().chain(between).sorted().collect_vec()
+        };
+        let (xs, ys): (Vec<_>, Vec<_>) = circumference
+            .iter()
+            .flat_map(|seg| match *seg {
+                Segment::Vert { x, y_min, y_max } => [(x, y_min), (x, y_max)],
+                Segment::Horiz { x_min, x_max, y } => [(x_min, y), (x_max, y)],
+            })
+            .unzip();
+        let (xs, ys) = (compress(xs), compress(ys));
+
+        let mut outside = vec![vec![0; xs.len() + 1]; ys.len() + 1];
+        for (j, &y) in ys.iter().enumerate() {
+            // Between each pair of vertical segments crossed going right, just above or just below
+            // the row, the tiles are inside (or on the loop)
+            let inside = |is_crossed: &dyn Fn(i64, i64) -> bool| {
+                let crossed = circumference.iter().filter_map(|seg| match *seg {
+                    Segment::Vert { x, y_min, y_max } if is_crossed(y_min, y_max) => Some(x),
+                    _ => None,
+                });
+                let intervals = crossed.sorted().tuples::<(_, _)>().collect_vec();
+                covered(&xs, &intervals)
+            };
+            let above = inside(&|y_min, y_max| y_min <= y && y < y_max);
+            let below = inside(&|y_min, y_max| y_min < y && y <= y_max);
+            for i in 0..xs.len() {
+                let is_outside = !above[i] && !below[i];
+                outside[j + 1][i + 1] =
+                    outside[j][i + 1] + outside[j + 1][i] - outside[j][i] + usize::from(is_outside);
+            }
+        }
+        Floor { xs, ys, outside }
+    }
+
+    /// Whether all the tiles of the rectangle, whose corners are red tiles, are red or green.
+    fn is_red_or_green(&self, r: &Rectangle) -> bool {
+        let index = |values: &[i64], v: i64| {
+            values
+                .binary_search(&v)
+                .expect("The corners are on the grid")
+        };
+        let (i1, i2) = (
+            index(&self.xs, r.top_left.x),
+            index(&self.xs, r.bottom_right.x) + 1,
+        );
+        let (j1, j2) = (
+            index(&self.ys, r.bottom_right.y),
+            index(&self.ys, r.top_left.y) + 1,
+        );
+        let o = &self.outside;
+        o[j2][i2] + o[j1][i1] == o[j1][i2] + o[j2][i1]
+    }
+}
+
+/// Whether each of the sorted `xs` is within one of the sorted and disjoint `intervals`.
+fn covered(xs: &[i64], intervals: &[(i64, i64)]) -> Vec<bool> {
+    let mut intervals = intervals.iter().peekable();
+    xs.iter()
+        .map(|&x| {
+            while intervals.next_if(|&&(_, end)| end < x).is_some() {}
+            intervals.peek().is_some_and(|&&(start, _)| start <= x)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::proptest::collection::vec;
+    use common::proptest::prelude::*;
+    use std::collections::{HashMap, HashSet};
+
+    common::example_tests! {
+        Day9,
+        ex1: { part_1: 50, part_2: 24 },
+        // A U-shaped loop: its notch is crossed by no segment, yet isn't red or green
+        ex2: { part_1: 121, part_2: 22 },
+    }
+
+    #[test]
+    fn test_validate() {
+        let errors = Day9::validate("7,1\n11,1\n11,7\n9,5\n");
+        assert_eq!(errors.iter().map(|e| e.line).collect_vec(), [4, 1]);
+        assert_eq!(Day9::validate("").len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_loop() {
+        assert!(part_2(&parse_input("7,1\n11,1\n11,7\n9,5\n").unwrap()).is_err());
+        assert_eq!(part_2(&[]).unwrap(), 0);
+        assert!(parse_input("0,1000000001\n").is_err());
+        assert!(Point::new(-1_000_000_001, 0).is_err());
+    }
+
+    /// The red tiles at the corners of the outline of a blob of cells grown at random from one
+    /// cell, then filled until the outline is a single loop: without holes, or cells touching only
+    /// by a corner.
+    fn outline(steps: &[(usize, u8)]) -> Vec<Point> {
+        let mut cells = vec![(0i64, 0i64)];
+        for &(i, direction) in steps {
+            let (x, y) = cells[i % cells.len()];
+            let cell = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)][direction as usize];
+            if !cells.contains(&cell) {
+                cells.push(cell);
+            }
+        }
+        let mut filled = cells.into_iter().collect::<HashSet<_>>();
+        loop {
+            let (x_min, x_max) = filled.iter().map(|c| c.0).minmax().into_option().unwrap();
+            let (y_min, y_max) = filled.iter().map(|c| c.1).minmax().into_option().unwrap();
+            let mut outside = HashSet::new();
+            let mut stack = vec![(x_min - 1, y_min - 1)];
+            while let Some((x, y)) = stack.pop() {
+                let in_box =
+                    (x_min - 1..=x_max + 1).contains(&x) && (y_min - 1..=y_max + 1).contains(&y);
+                if in_box && !filled.contains(&(x, y)) && outside.insert((x, y)) {
+                    stack.extend([(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+                }
+            }
+            let holes = (x_min..=x_max)
+                .cartesian_product(y_min..=y_max)
+                .filter(|c| !filled.contains(c) && !outside.contains(c));
+            let mut missing = holes.collect_vec();
+            for &(x, y) in &filled {
+                for y2 in [y - 1, y + 1] {
+                    let pinch = filled.contains(&(x + 1, y2))
+                        && !filled.contains(&(x + 1, y))
+                        && !filled.contains(&(x, y2));
+                    if pinch {
+                        missing.push((x + 1, y));
+                    }
+                }
+            }
+            if missing.is_empty() {
+                break;
+            }
+            filled.extend(missing);
+        }
+
+        // The sides of the cells on the outline, counterclockwise
+        let mut next = HashMap::new();
+        for &(x, y) in &filled {
+            let sides = [
+                ((x, y - 1), (x, y), (x + 1, y)),
+                ((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+                ((x, y + 1), (x + 1, y + 1), (x, y + 1)),
+                ((x - 1, y), (x, y + 1), (x, y)),
+            ];
+            for (neighbor, from, to) in sides {
+                if !filled.contains(&neighbor) {
+                    next.insert(from, to);
+                }
+            }
+        }
+        let start = *next.keys().min().unwrap();
+        let mut corners = Vec::new();
+        let mut current = start;
+        loop {
+            let (previous, following) = (current, next[&current]);
+            let after = next[&following];
+            let turns = (following.0 - previous.0, following.1 - previous.1)
+                != (after.0 - following.0, after.1 - following.1);
+            if turns {
+                corners.push(Point { x: following.0, y: following.1 });
+            }
+            current = following;
+            if current == start {
+                break;
+            }
+        }
+        corners
+    }
+
+    /// Whether a tile is on the loop or inside it, casting a ray to the right between two rows.
+    fn is_red_or_green(tiles: &[Point], x: i64, y: i64) -> bool {
+        let segments = tiles.iter().circular_tuple_windows::<(_, _)>();
+        let on_loop = segments.clone().any(|(p1, p2)| {
+            (p1.x.min(p2.x)..=p1.x.max(p2.x)).contains(&x)
+                && (p1.y.min(p2.y)..=p1.y.max(p2.y)).contains(&y)
+        });
+        let crossings = segments
+            .filter(|(p1, p2)| {
+                p1.x == p2.x && p1.x > x && (p1.y.min(p2.y)..p1.y.max(p2.y)).contains(&y)
+            })
+            .count();
+        on_loop || crossings % 2 == 1
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let brute_force = |tiles: &Vec<Point>| {
+            tiles
+                .iter()
+                .tuple_combinations::<(_, _)>()
+                .map(|(p1, p2)| Rectangle::new(p1, p2))
+                .filter(|r| {
+                    (r.top_left.x..=r.bottom_right.x).all(|x| {
+                        (r.bottom_right.y..=r.top_left.y).all(|y| is_red_or_green(tiles, x, y))
+                    })
+                })
+                .map(|r| r.area())
+                .max()
+                .unwrap_or(0)
+        };
+        let loops = vec((0usize..100, 0u8..4), 0..15).prop_map(|steps| outline(&steps));
+        common::check_against_reference(loops, brute_force, |tiles| part_2(tiles).unwrap());
+    }
+
+    #[test]
+    fn test_generate() {
+        common::check_generator::<Day9>(20);
+    }
+}